@@ -29,6 +29,9 @@ pub struct Constants {
     pub initial_window_height: f32,
     pub vsync: bool,
     pub fullscreen: bool,
+    /// Runs fullscreen with the cursor hidden, saving to the library and quitting on any input
+    #[serde(default)]
+    pub screensaver: bool,
     pub console_width: usize,
     pub fancy_terminal: bool,
 
@@ -54,6 +57,9 @@ pub struct Constants {
     pub noise_t_scale_minimum: f64,
 
     pub graph_mutation_divisor: usize,
+    /// Older constants.yml files predate the undo stack, so it falls back to a sensible length
+    #[serde(default = "default_undo_stack_length")]
+    pub undo_stack_length: usize,
 
     pub activity_value_upper_bound: f64,
     pub activity_value_lower_bound: f64,
@@ -92,6 +98,11 @@ pub struct Constants {
     pub min_branch_depth: usize,
     pub max_branch_depth: usize,
 
+    pub adaptive_resolution: Option<AdaptiveResolutionConfig>,
+    pub output_palette: Option<OutputPaletteConfig>,
+    pub post_process: Option<PostProcessConfig>,
+    pub video_wall: Option<VideoWallConfig>,
+
     pub mic: Option<MicConfig>,
 
     pub smithsonian_api_key: Option<String>,
@@ -102,6 +113,68 @@ pub struct Constants {
     pub mutagen_profiler_graphs: bool,
 }
 
+fn default_undo_stack_length() -> usize {
+    16
+}
+
+#[derive(Clone, Deserialize)]
+pub struct AdaptiveResolutionConfig {
+    /// The smallest fraction of cell_array_width/height we're allowed to shrink to
+    pub min_scale: f32,
+    pub scale_step: f32,
+    /// Fraction of the update budget (1 / target_fps) above which we shrink
+    pub upper_load: f64,
+    /// Fraction of the update budget below which we grow back towards full resolution
+    pub lower_load: f64,
+    pub load_lerp_factor: f64,
+    pub cooldown_updates: usize,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+pub enum Dithering {
+    None,
+    /// A 4x4 Bayer matrix, cheap and stable from frame to frame
+    Ordered,
+    /// Floyd-Steinberg, run over the whole frame once its last slice has been computed
+    ErrorDiffusion,
+}
+
+/// ggez only drives a single window, so a wall of N displays is covered by one borderless window
+/// spanning all of them, split into a tile per display
+#[derive(Clone, Deserialize)]
+pub struct VideoWallConfig {
+    pub tiles: Vec<VideoWallTileConfig>,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct VideoWallTileConfig {
+    /// Where the tile sits in the window, in pixels
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// The centre of the part of the frame shown on this tile, from -1 to 1 on each axis
+    pub offset: [f32; 2],
+    /// How much of the frame the tile shows along each axis, where 1 is the whole frame
+    pub scale: [f32; 2],
+}
+
+#[derive(Clone, Deserialize)]
+pub struct PostProcessConfig {
+    /// Directory of Shadertoy style .glsl files, run in filename order over the rendered frame
+    pub shader_dir: String,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct OutputPaletteConfig {
+    /// Hex colours such as "#ff8800"
+    #[serde(default)]
+    pub colors: Vec<String>,
+    /// A GIMP .gpl file or a .hex file with one colour per line, added to colors
+    pub file: Option<String>,
+    pub dithering: Dithering,
+}
+
 #[derive(Clone, Deserialize)]
 pub struct MicConfig {
     pub min_frequency: f32,
@@ -1,2 +1,17 @@
+pub mod blend;
+pub mod escape_time;
+pub mod flock;
 pub mod frame_renderers;
+pub mod gray_scott;
 pub mod image;
+pub mod kernel;
+pub mod lenia;
+pub mod lsystem;
+pub mod palette;
+pub mod physarum;
+pub mod polar;
+pub mod sdf;
+pub mod sequence;
+pub mod totalistic;
+pub mod turmite;
+pub mod worley;
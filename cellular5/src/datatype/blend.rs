@@ -0,0 +1,240 @@
+use crate::prelude::*;
+
+//Blend modes follow the W3C compositing spec, where a is the backdrop and b is the colour being
+//blended onto it. Alpha is always opaque, matching the other blend nodes.
+
+type Rgb = [f32; 3];
+
+fn to_rgb(color: FloatColor) -> Rgb {
+    [
+        color.r.into_inner(),
+        color.g.into_inner(),
+        color.b.into_inner(),
+    ]
+}
+
+fn from_rgb(rgb: Rgb) -> FloatColor {
+    //Clamped rather than checked, since float error can land a hair outside the range
+    FloatColor {
+        r: UNFloat::new_clamped(rgb[0]),
+        g: UNFloat::new_clamped(rgb[1]),
+        b: UNFloat::new_clamped(rgb[2]),
+        a: UNFloat::ONE,
+    }
+}
+
+/// Applies a per channel blend function to each of r, g and b
+pub fn blend_separable(a: FloatColor, b: FloatColor, f: fn(f32, f32) -> f32) -> FloatColor {
+    let a = to_rgb(a);
+    let b = to_rgb(b);
+
+    from_rgb([f(a[0], b[0]), f(a[1], b[1]), f(a[2], b[2])])
+}
+
+/// Applies a blend function that mixes channels, such as swapping hue or luminosity
+pub fn blend_non_separable(a: FloatColor, b: FloatColor, f: fn(Rgb, Rgb) -> Rgb) -> FloatColor {
+    from_rgb(f(to_rgb(a), to_rgb(b)))
+}
+
+pub fn multiply_channel(a: f32, b: f32) -> f32 {
+    a * b
+}
+
+pub fn screen_channel(a: f32, b: f32) -> f32 {
+    a + b - a * b
+}
+
+pub fn overlay_channel(a: f32, b: f32) -> f32 {
+    hard_light_channel(b, a)
+}
+
+pub fn color_dodge_channel(a: f32, b: f32) -> f32 {
+    if a <= 0.0 {
+        0.0
+    } else if b >= 1.0 {
+        1.0
+    } else {
+        (a / (1.0 - b)).min(1.0)
+    }
+}
+
+pub fn color_burn_channel(a: f32, b: f32) -> f32 {
+    if a >= 1.0 {
+        1.0
+    } else if b <= 0.0 {
+        0.0
+    } else {
+        1.0 - ((1.0 - a) / b).min(1.0)
+    }
+}
+
+pub fn hard_light_channel(a: f32, b: f32) -> f32 {
+    if b <= 0.5 {
+        multiply_channel(a, 2.0 * b)
+    } else {
+        screen_channel(a, 2.0 * b - 1.0)
+    }
+}
+
+pub fn soft_light_channel(a: f32, b: f32) -> f32 {
+    if b <= 0.5 {
+        a - (1.0 - 2.0 * b) * a * (1.0 - a)
+    } else {
+        let d = if a <= 0.25 {
+            ((16.0 * a - 12.0) * a + 4.0) * a
+        } else {
+            a.sqrt()
+        };
+
+        a + (2.0 * b - 1.0) * (d - a)
+    }
+}
+
+pub fn difference_channel(a: f32, b: f32) -> f32 {
+    (a - b).abs()
+}
+
+pub fn exclusion_channel(a: f32, b: f32) -> f32 {
+    a + b - 2.0 * a * b
+}
+
+fn lum(c: Rgb) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn sat(c: Rgb) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+/// Pulls any out of range channels back in while keeping luminosity
+fn clip_color(c: Rgb) -> Rgb {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+
+    let mut c = c;
+
+    if n < 0.0 {
+        c = [
+            l + (c[0] - l) * l / (l - n),
+            l + (c[1] - l) * l / (l - n),
+            l + (c[2] - l) * l / (l - n),
+        ];
+    }
+
+    if x > 1.0 {
+        c = [
+            l + (c[0] - l) * (1.0 - l) / (x - l),
+            l + (c[1] - l) * (1.0 - l) / (x - l),
+            l + (c[2] - l) * (1.0 - l) / (x - l),
+        ];
+    }
+
+    c
+}
+
+fn set_lum(c: Rgb, l: f32) -> Rgb {
+    let d = l - lum(c);
+
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+fn set_sat(c: Rgb, s: f32) -> Rgb {
+    let mut order = [0, 1, 2];
+    order.sort_by(|i, j| c[*i].partial_cmp(&c[*j]).unwrap());
+    let [min, mid, max] = order;
+
+    let mut result = [0.0; 3];
+
+    if c[max] > c[min] {
+        result[mid] = (c[mid] - c[min]) * s / (c[max] - c[min]);
+        result[max] = s;
+    }
+
+    result
+}
+
+pub fn hue_blend(a: Rgb, b: Rgb) -> Rgb {
+    set_lum(set_sat(b, sat(a)), lum(a))
+}
+
+pub fn saturation_blend(a: Rgb, b: Rgb) -> Rgb {
+    set_lum(set_sat(a, sat(b)), lum(a))
+}
+
+pub fn color_blend(a: Rgb, b: Rgb) -> Rgb {
+    set_lum(b, lum(a))
+}
+
+pub fn luminosity_blend(a: Rgb, b: Rgb) -> Rgb {
+    set_lum(a, lum(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 0.0001;
+
+    fn in_range(v: f32) -> bool {
+        (-EPSILON..=1.0 + EPSILON).contains(&v)
+    }
+
+    fn samples() -> Vec<f32> {
+        (0..=8).map(|i| i as f32 / 8.0).collect()
+    }
+
+    #[test]
+    fn separable_modes_stay_in_range() {
+        let modes: &[fn(f32, f32) -> f32] = &[
+            multiply_channel,
+            screen_channel,
+            overlay_channel,
+            color_dodge_channel,
+            color_burn_channel,
+            hard_light_channel,
+            soft_light_channel,
+            difference_channel,
+            exclusion_channel,
+        ];
+
+        for mode in modes {
+            for a in samples() {
+                for b in samples() {
+                    assert!(in_range(mode(a, b)), "{} {} -> {}", a, b, mode(a, b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_separable_modes_stay_in_range() {
+        let modes: &[fn(Rgb, Rgb) -> Rgb] =
+            &[hue_blend, saturation_blend, color_blend, luminosity_blend];
+
+        let colors: Vec<Rgb> = samples()
+            .into_iter()
+            .step_by(2)
+            .flat_map(|r| {
+                samples().into_iter().step_by(2).flat_map(move |g| {
+                    samples().into_iter().step_by(2).map(move |b| [r, g, b])
+                })
+            })
+            .collect();
+
+        for mode in modes {
+            for a in &colors {
+                for b in &colors {
+                    let result = mode(*a, *b);
+                    assert!(
+                        result.iter().all(|v| in_range(*v)),
+                        "{:?} {:?} -> {:?}",
+                        a,
+                        b,
+                        result
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::prelude::*;
+
+use na::Complex;
+
+//Large so the smooth iteration count has room to settle
+const BAILOUT_RADIUS_SQUARED: f64 = 256.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EscapeTimeFormula {
+    /// z^power + c, covering the Mandelbrot and Julia sets
+    Multibrot { power: u32 },
+    /// (|re| + i|im|)^2 + c
+    BurningShip,
+    /// conj(z)^2 + c
+    Tricorn,
+}
+
+impl EscapeTimeFormula {
+    fn step(self, z: Complex<f64>, c: Complex<f64>) -> Complex<f64> {
+        match self {
+            EscapeTimeFormula::Multibrot { power } => z.powu(power) + c,
+            EscapeTimeFormula::BurningShip => {
+                let z = Complex::new(z.re.abs(), z.im.abs());
+                z * z + c
+            }
+            EscapeTimeFormula::Tricorn => {
+                let z = z.conj();
+                z * z + c
+            }
+        }
+    }
+
+    fn power(self) -> f64 {
+        match self {
+            EscapeTimeFormula::Multibrot { power } => f64::from(power),
+            EscapeTimeFormula::BurningShip | EscapeTimeFormula::Tricorn => 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct EscapeTimeResult {
+    pub z_final: Complex<f64>,
+    pub max_iterations: usize,
+    /// Continuous iteration count, or None if the point never escaped
+    pub smooth_iterations: Option<f64>,
+}
+
+impl EscapeTimeResult {
+    /// Points that never escape are given the maximum smooth count
+    pub fn to_smooth_result(self, normaliser: SFloatNormaliser) -> SmoothIterativeResult {
+        let smooth_iterations = self.smooth_iterations.map_or(1.0, |smooth| {
+            (smooth / self.max_iterations as f64).max(0.0).min(1.0) as f32
+        });
+
+        SmoothIterativeResult {
+            z_final: SNComplex::new_normalised(self.z_final, normaliser),
+            smooth_iterations: UNFloat::new(smooth_iterations),
+        }
+    }
+}
+
+/// Like IterativeResult, but keeping the continuous iteration count so colouring doesn't band
+#[derive(Debug, Clone, Copy)]
+pub struct SmoothIterativeResult {
+    pub z_final: SNComplex,
+    /// The smooth iteration count over the maximum number of iterations
+    pub smooth_iterations: UNFloat,
+}
+
+impl SmoothIterativeResult {
+    /// IterativeResult only stores a byte, so the smooth count is spread over its whole range
+    pub fn to_iterative_result(self) -> IterativeResult {
+        IterativeResult::new(
+            self.z_final,
+            Byte::new((self.smooth_iterations.into_inner() * 255.0).round() as u8),
+        )
+    }
+}
+
+pub fn escape_time(
+    formula: EscapeTimeFormula,
+    z: Complex<f64>,
+    c: Complex<f64>,
+    max_iterations: usize,
+) -> EscapeTimeResult {
+    let mut z = z;
+
+    for i in 0..max_iterations {
+        z = formula.step(z, c);
+
+        let norm_sqr = z.norm_sqr();
+
+        if norm_sqr > BAILOUT_RADIUS_SQUARED {
+            // log|z| = log(|z|^2) / 2
+            let log_z = norm_sqr.ln() * 0.5;
+            let smooth = i as f64 + 1.0 - log_z.ln() / formula.power().ln();
+
+            return EscapeTimeResult {
+                z_final: z,
+                max_iterations,
+                smooth_iterations: Some(smooth.max(0.0)),
+            };
+        }
+    }
+
+    EscapeTimeResult {
+        z_final: z,
+        max_iterations,
+        smooth_iterations: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn origin_never_escapes() {
+        let result = escape_time(
+            EscapeTimeFormula::Multibrot { power: 2 },
+            Complex::new(0.0, 0.0),
+            Complex::new(0.0, 0.0),
+            64,
+        );
+
+        assert!(result.smooth_iterations.is_none());
+    }
+
+    #[test]
+    fn smooth_count_is_continuous() {
+        let formula = EscapeTimeFormula::Multibrot { power: 2 };
+        let a = escape_time(formula, Complex::new(0.0, 0.0), Complex::new(0.5, 0.5), 64);
+        let b = escape_time(formula, Complex::new(0.0, 0.0), Complex::new(0.5, 0.501), 64);
+
+        let (a, b) = (a.smooth_iterations.unwrap(), b.smooth_iterations.unwrap());
+        assert!((a - b).abs() < 0.5);
+    }
+
+    #[test]
+    fn smooth_result_keeps_fractional_counts() {
+        let formula = EscapeTimeFormula::Multibrot { power: 2 };
+        let a = escape_time(formula, Complex::new(0.0, 0.0), Complex::new(0.5, 0.5), 64)
+            .to_smooth_result(SFloatNormaliser::SinRepeating);
+        let b = escape_time(formula, Complex::new(0.0, 0.0), Complex::new(0.5, 0.501), 64)
+            .to_smooth_result(SFloatNormaliser::SinRepeating);
+
+        assert!(a.smooth_iterations.into_inner() > 0.0);
+        assert!(a.smooth_iterations.into_inner() < 1.0);
+        assert!(
+            (a.smooth_iterations.into_inner() - b.smooth_iterations.into_inner()).abs()
+                < 1.0 / 64.0
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use rand::prelude::*;
+
+use crate::prelude::*;
+
+use na::{Point2, Vector2};
+
+const NEIGHBOUR_RADIUS: f32 = 0.3;
+const SEPARATION_RADIUS: f32 = 0.08;
+//In units per second, scaled down by the target fps each tick
+const MAX_SPEED: f32 = 0.5;
+const MAX_FORCE: f32 = 0.05;
+//A point set that starts out empty would otherwise never have anything to flock
+const MIN_BOIDS: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct FlockWeights {
+    pub separation: f32,
+    pub alignment: f32,
+    pub cohesion: f32,
+}
+
+impl FlockWeights {
+    pub fn from_unfloats(separation: UNFloat, alignment: UNFloat, cohesion: UNFloat) -> Self {
+        Self {
+            //Separation needs to win out or every flock collapses into a single point
+            separation: separation.into_inner() * 2.0,
+            alignment: alignment.into_inner(),
+            cohesion: cohesion.into_inner(),
+        }
+    }
+}
+
+/// The shortest offset from a to b, since the flock wraps around the edges
+fn wrapped_offset(a: Point2<f32>, b: Point2<f32>) -> Vector2<f32> {
+    let wrap = |d: f32| (d + 1.0).rem_euclid(2.0) - 1.0;
+    let offset = b - a;
+
+    Vector2::new(wrap(offset.x), wrap(offset.y))
+}
+
+fn limit(v: Vector2<f32>, max: f32) -> Vector2<f32> {
+    let norm = v.norm();
+
+    if norm > max {
+        v * (max / norm)
+    } else {
+        v
+    }
+}
+
+/// Advances each boid by one tick, returning the new positions. Velocities are kept between
+/// ticks and resized to match the number of points, so the point set can change size freely.
+/// Boids are spawned at random until there are at least MIN_BOIDS.
+pub fn step_flock<F: FnMut(SNPoint) -> FlockWeights>(
+    points: &[SNPoint],
+    velocities: &mut Vec<Vector2<f32>>,
+    get_weights: F,
+) -> Vec<SNPoint> {
+    let mut rng = thread_rng();
+    let mut points = points.to_vec();
+
+    while points.len() < MIN_BOIDS {
+        points.push(SNPoint::new(Point2::new(
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+        )));
+    }
+
+    step(
+        &points,
+        velocities,
+        MAX_SPEED / CONSTS.target_fps as f32,
+        MAX_FORCE / CONSTS.target_fps as f32,
+        get_weights,
+    )
+}
+
+fn step<F: FnMut(SNPoint) -> FlockWeights>(
+    points: &[SNPoint],
+    velocities: &mut Vec<Vector2<f32>>,
+    max_speed: f32,
+    max_force: f32,
+    mut get_weights: F,
+) -> Vec<SNPoint> {
+    let mut rng = thread_rng();
+    velocities.truncate(points.len());
+    while velocities.len() < points.len() {
+        velocities.push(Vector2::new(
+            rng.gen_range(-max_speed..=max_speed),
+            rng.gen_range(-max_speed..=max_speed),
+        ));
+    }
+
+    let new_velocities: Vec<_> = points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            let position = point.into_inner();
+
+            let mut separation = Vector2::zeros();
+            let mut alignment = Vector2::zeros();
+            let mut cohesion = Vector2::zeros();
+            let mut neighbours = 0;
+
+            for (j, other) in points.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+
+                let offset = wrapped_offset(position, other.into_inner());
+                let distance = offset.norm();
+
+                if distance < NEIGHBOUR_RADIUS {
+                    alignment += velocities[j];
+                    cohesion += offset;
+                    neighbours += 1;
+
+                    if distance < SEPARATION_RADIUS && distance > 0.0 {
+                        separation -= offset / (distance * distance);
+                    }
+                }
+            }
+
+            let velocity = velocities[i];
+
+            if neighbours == 0 {
+                return limit(velocity, max_speed);
+            }
+
+            let steer = |desired: Vector2<f32>| {
+                if desired.norm() > 0.0 {
+                    limit(desired.normalize() * max_speed - velocity, max_force)
+                } else {
+                    Vector2::zeros()
+                }
+            };
+
+            let weights = get_weights(*point);
+
+            limit(
+                velocity
+                    + steer(separation) * weights.separation
+                    + steer(alignment / neighbours as f32) * weights.alignment
+                    + steer(cohesion / neighbours as f32) * weights.cohesion,
+                max_speed,
+            )
+        })
+        .collect();
+
+    *velocities = new_velocities;
+
+    points
+        .iter()
+        .zip(velocities.iter())
+        .map(|(point, velocity)| {
+            let position = point.into_inner() + velocity;
+
+            SNPoint::new(Point2::new(
+                (position.x + 1.0).rem_euclid(2.0) - 1.0,
+                (position.y + 1.0).rem_euclid(2.0) - 1.0,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEED: f32 = 0.01;
+    const FORCE: f32 = 0.001;
+
+    fn weights(separation: f32, alignment: f32, cohesion: f32) -> FlockWeights {
+        FlockWeights {
+            separation,
+            alignment,
+            cohesion,
+        }
+    }
+
+    fn point(x: f32, y: f32) -> SNPoint {
+        SNPoint::new(Point2::new(x, y))
+    }
+
+    #[test]
+    fn separation_pushes_apart() {
+        let points = [point(0.0, 0.0), point(0.01, 0.0)];
+        let mut velocities = vec![Vector2::zeros(); 2];
+
+        let new_points = step(&points, &mut velocities, SPEED, FORCE, |_| {
+            weights(1.0, 0.0, 0.0)
+        });
+
+        let before = wrapped_offset(points[0].into_inner(), points[1].into_inner()).norm();
+        let after = wrapped_offset(new_points[0].into_inner(), new_points[1].into_inner()).norm();
+
+        assert!(after > before, "{} -> {}", before, after);
+    }
+
+    #[test]
+    fn velocity_is_clamped() {
+        //One pair close enough to be neighbours, and one boid on its own
+        let points = [point(0.0, 0.0), point(0.1, 0.0), point(0.7, 0.7)];
+        let mut velocities = vec![Vector2::new(1.0, -1.0); 3];
+
+        step(&points, &mut velocities, SPEED, FORCE, |_| {
+            weights(2.0, 1.0, 1.0)
+        });
+
+        for velocity in &velocities {
+            assert!(velocity.norm() <= SPEED + 0.0001, "{}", velocity.norm());
+        }
+    }
+
+    #[test]
+    fn points_stay_wrapped() {
+        let mut points = vec![point(0.999, 0.999), point(-0.999, -0.999), point(0.999, -0.999)];
+        let mut velocities = vec![
+            Vector2::new(SPEED, SPEED),
+            Vector2::new(-SPEED, -SPEED),
+            Vector2::new(SPEED, -SPEED),
+        ];
+
+        for _ in 0..100 {
+            points = step(&points, &mut velocities, SPEED, FORCE, |_| {
+                weights(1.0, 1.0, 1.0)
+            });
+
+            for p in &points {
+                let p = p.into_inner();
+                assert!((-1.0..=1.0).contains(&p.x) && (-1.0..=1.0).contains(&p.y), "{}", p);
+            }
+        }
+    }
+}
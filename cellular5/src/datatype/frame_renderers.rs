@@ -1,9 +1,10 @@
 use std::f32::consts::PI;
 
 use ggez::{
-    graphics::{Color as GgColor, DrawParam, Image as GgImage},
+    graphics::{BlendMode, Color as GgColor, DrawParam, Image as GgImage, Mesh, Vertex},
     Context, GameResult,
 };
+use ndarray::Array3;
 
 use crate::prelude::*;
 
@@ -40,6 +41,12 @@ impl<'a> RenderArgs<'a> {
         (self.prev_history_index() + 1) % self.history_len()
     }
 
+    /// The tic the current history step was computed on, which unlike history_index doesn't
+    /// jump around as the ring buffer wraps
+    pub fn history_t(&self) -> usize {
+        (self.current_t + self.lerp_i).saturating_sub(self.lerp_len())
+    }
+
     pub fn prev_history_step(&self) -> &'a HistoryStep {
         &self.history.history_steps[self.prev_history_index()]
     }
@@ -51,6 +58,135 @@ impl<'a> RenderArgs<'a> {
     pub fn history_len(&self) -> usize {
         self.history.history_steps.len()
     }
+
+    /// The scale needed to stretch the current history step's texture over the whole window
+    pub fn texture_scale(&self) -> (f32, f32) {
+        let texture = &self.history_step().computed_texture;
+
+        (
+            CONSTS.initial_window_width / texture.width() as f32,
+            CONSTS.initial_window_height / texture.height() as f32,
+        )
+    }
+}
+
+/// How a history step's texture is blended onto the frame. Anything other than alpha blending is
+/// only used for one history step in every interval, since stacking a whole history of additive
+/// or multiplicative draws washes the screen out to white or black. Modes like Multiply and Darken
+/// ignore the draw's alpha, so the interval is also stretched to cover every step drawn at once.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameBlend {
+    mode: BlendMode,
+    interval: usize,
+}
+
+impl FrameBlend {
+    const MIN_INTERVAL: usize = 2;
+
+    pub fn new(mode: BlendMode, interval: usize) -> Self {
+        Self {
+            //Replace throws away the rest of the history and Invert strobes, so neither is allowed
+            mode: match mode {
+                BlendMode::Replace | BlendMode::Invert => BlendMode::Alpha,
+                mode => mode,
+            },
+            interval: interval.max(Self::MIN_INTERVAL),
+        }
+    }
+
+    /// The mode for the history step computed on tic t, when in_flight history steps are drawn
+    /// each frame
+    pub fn mode_at(self, t: usize, in_flight: usize) -> BlendMode {
+        if t % self.interval.max(in_flight) == 0 {
+            self.mode
+        } else {
+            BlendMode::Alpha
+        }
+    }
+}
+
+impl Default for FrameBlend {
+    fn default() -> Self {
+        Self::new(BlendMode::Alpha, Self::MIN_INTERVAL)
+    }
+}
+
+/// Grid cells per side of the mesh that displacement feedback warps the previous frame over
+const DISPLACEMENT_GRID: usize = 32;
+/// The furthest displacement feedback can push a texture coordinate
+const MAX_DISPLACEMENT: f32 = 0.05;
+/// The furthest a channel can be split away from green, as a fraction of the window
+const MAX_CHANNEL_SPLIT: f32 = 0.02;
+/// The most a channel can be twisted away from green, in radians
+const MAX_CHANNEL_TWIST: f32 = 0.05;
+
+fn luminance_at(cell_array: &Array3<u8>, u: f32, v: f32) -> f32 {
+    let (height, width, _) = cell_array.dim();
+    let col = ((u * width as f32) as usize).min(width - 1);
+    let row = ((v * height as f32) as usize).min(height - 1);
+
+    (0.299 * f32::from(cell_array[[row, col, 0]])
+        + 0.587 * f32::from(cell_array[[row, col, 1]])
+        + 0.114 * f32::from(cell_array[[row, col, 2]]))
+        / 255.0
+}
+
+/// Builds a window sized grid textured with texture, where each vertex's texture coordinate is
+/// pushed along the luminance gradient of flow_field, rotated by flow_angle
+fn displacement_mesh(
+    ctx: &mut Context,
+    texture: &GgImage,
+    flow_field: &Array3<u8>,
+    strength: f32,
+    flow_angle: f32,
+    alpha: f32,
+) -> GameResult<Mesh> {
+    let step = 1.0 / DISPLACEMENT_GRID as f32;
+    let (sin, cos) = flow_angle.sin_cos();
+
+    let mut vertices = Vec::with_capacity((DISPLACEMENT_GRID + 1) * (DISPLACEMENT_GRID + 1));
+
+    for gy in 0..=DISPLACEMENT_GRID {
+        for gx in 0..=DISPLACEMENT_GRID {
+            let u = gx as f32 * step;
+            let v = gy as f32 * step;
+
+            let dx = luminance_at(flow_field, (u + step).min(1.0), v)
+                - luminance_at(flow_field, (u - step).max(0.0), v);
+            let dy = luminance_at(flow_field, u, (v + step).min(1.0))
+                - luminance_at(flow_field, u, (v - step).max(0.0));
+
+            let flow_x = (dx * cos - dy * sin) * strength * MAX_DISPLACEMENT;
+            let flow_y = (dx * sin + dy * cos) * strength * MAX_DISPLACEMENT;
+
+            vertices.push(Vertex {
+                pos: [
+                    u * CONSTS.initial_window_width,
+                    v * CONSTS.initial_window_height,
+                ],
+                uv: [(u + flow_x).clamp(0.0, 1.0), (v + flow_y).clamp(0.0, 1.0)],
+                color: [1.0, 1.0, 1.0, alpha],
+            });
+        }
+    }
+
+    Mesh::from_raw(ctx, &vertices, &displacement_indices(), Some(texture.clone()))
+}
+
+/// Two triangles for every cell of the displacement grid
+fn displacement_indices() -> Vec<u32> {
+    let row = (DISPLACEMENT_GRID + 1) as u32;
+    let mut indices = Vec::with_capacity(DISPLACEMENT_GRID * DISPLACEMENT_GRID * 6);
+
+    for gy in 0..DISPLACEMENT_GRID as u32 {
+        for gx in 0..DISPLACEMENT_GRID as u32 {
+            let i = gy * row + gx;
+
+            indices.extend_from_slice(&[i, i + 1, i + row, i + 1, i + row + 1, i + row]);
+        }
+    }
+
+    indices
 }
 
 #[derive(Debug)]
@@ -98,12 +234,30 @@ pub enum FrameRenderers {
         from_scale_scalar: UNFloat,
         to_scale_scalar: UNFloat,
     },
+    /// Warps the previous history step, using the current one's luminance as a flow field
+    DisplacementFeedback {
+        strength: UNFloat,
+        flow_angle: Angle,
+    },
+    /// Draws red and blue with their own offset and rotation, leaving green in place
+    ChannelSplit {
+        red_offset: SNPoint,
+        blue_offset: SNPoint,
+        spread: UNFloat,
+        twist: SNFloat,
+    },
     /// Used as a default value for history steps before they first time they're computed to
     None,
 }
 
 impl FrameRenderers {
     pub fn draw(&self, args: RenderArgs) -> GameResult<()> {
+        let blend_mode = args
+            .history_step()
+            .blend
+            .mode_at(args.history_t(), args.lerp_len());
+        ggez::graphics::set_blend_mode(args.ctx, blend_mode)?;
+
         match self {
             FrameRenderers::BasicFade => {
                 let original_alpha = 1.0 - args.back_lerp_val();
@@ -113,8 +267,7 @@ impl FrameRenderers {
                 let dest_x = CONSTS.initial_window_width * 0.5;
                 let dest_y = CONSTS.initial_window_height * 0.5;
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
                 ggez::graphics::draw(
                     args.ctx,
                     &args.history_step().computed_texture,
@@ -138,8 +291,7 @@ impl FrameRenderers {
 
                     let scalar = 1.0 - ((args.lerp_i) as f32 / args.lerp_len() as f32);
 
-                    let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                    let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                    let (scale_x, scale_y) = args.texture_scale();
                     ggez::graphics::draw(
                         args.ctx,
                         &args.history_step().computed_texture,
@@ -159,8 +311,7 @@ impl FrameRenderers {
                 let dest_x = CONSTS.initial_window_width * 0.5;
                 let dest_y = CONSTS.initial_window_height * 0.5;
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
                 //TODO fix
                 let invert = (args
                     .history_step()
@@ -243,8 +394,7 @@ impl FrameRenderers {
                     0.0
                 };
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
 
                 let offset_y = if invert.into_inner() { 1.0 } else { 0.0 };
 
@@ -272,8 +422,7 @@ impl FrameRenderers {
                 let dest_x = CONSTS.initial_window_width * 0.5;
                 let dest_y = CONSTS.initial_window_height * 0.5;
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
 
                 let x_scalar;
                 let y_scalar;
@@ -332,8 +481,7 @@ impl FrameRenderers {
                 let dest_x = CONSTS.initial_window_width * 0.5;
                 let dest_y = CONSTS.initial_window_height * 0.5;
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
                 ggez::graphics::draw(
                     args.ctx,
                     &args.history_step().computed_texture,
@@ -373,8 +521,7 @@ impl FrameRenderers {
                 let dest_x = CONSTS.initial_window_width * 0.5;
                 let dest_y = CONSTS.initial_window_height * 0.5;
 
-                let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (scale_x, scale_y) = args.texture_scale();
                 ggez::graphics::draw(
                     args.ctx,
                     &args.history_step().computed_texture,
@@ -405,8 +552,7 @@ impl FrameRenderers {
                     let dest_x = CONSTS.initial_window_width * 0.5;
                     let dest_y = CONSTS.initial_window_height * 0.5;
 
-                    let scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                    let scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                    let (scale_x, scale_y) = args.texture_scale();
 
                     let t_offset = if invert_t_offset.into_inner() {
                         args.lerp_len() - args.lerp_i
@@ -456,8 +602,7 @@ impl FrameRenderers {
                 let mut offset_x = 0.5;
                 let mut offset_y = 0.5;
 
-                let mut scale_x = CONSTS.initial_window_width / CONSTS.cell_array_width as f32;
-                let mut scale_y = CONSTS.initial_window_height / CONSTS.cell_array_height as f32;
+                let (mut scale_x, mut scale_y) = args.texture_scale();
 
                 let rotation: f32 = 0.0;
 
@@ -605,6 +750,91 @@ impl FrameRenderers {
                 )?;
             }
 
+            FrameRenderers::DisplacementFeedback {
+                strength,
+                flow_angle,
+            } => {
+                let original_alpha = 1.0 - args.back_lerp_val();
+                let alpha = (1.0 - ((original_alpha * 2.0) - 1.0).abs())
+                    / CONSTS.cell_array_lerp_length as f32;
+
+                let mesh = displacement_mesh(
+                    args.ctx,
+                    &args.prev_history_step().computed_texture,
+                    &args.history_step().cell_array,
+                    strength.into_inner(),
+                    flow_angle.into_inner(),
+                    (1.0 / args.history_len() as f32) * alpha,
+                )?;
+
+                ggez::graphics::draw(args.ctx, &mesh, DrawParam::new())?;
+            }
+            FrameRenderers::ChannelSplit {
+                red_offset,
+                blue_offset,
+                spread,
+                twist,
+            } => {
+                let original_alpha = 1.0 - args.back_lerp_val();
+                let alpha = (1.0 - ((original_alpha * 2.0) - 1.0).abs())
+                    / CONSTS.cell_array_lerp_length as f32
+                    / args.history_len() as f32;
+
+                let dest_x = CONSTS.initial_window_width * 0.5;
+                let dest_y = CONSTS.initial_window_height * 0.5;
+
+                let (scale_x, scale_y) = args.texture_scale();
+
+                //Darkening by alpha then adding each channel back at alpha comes out the same as
+                //an alpha blend wherever the channels line up
+                if blend_mode == BlendMode::Alpha {
+                    ggez::graphics::set_blend_mode(args.ctx, BlendMode::Alpha)?;
+                    ggez::graphics::draw(
+                        args.ctx,
+                        args.blank_texture,
+                        DrawParam::new().color(GgColor::new(0.0, 0.0, 0.0, alpha)).scale([
+                            CONSTS.initial_window_width,
+                            CONSTS.initial_window_height,
+                        ]),
+                    )?;
+
+                    ggez::graphics::set_blend_mode(args.ctx, BlendMode::Add)?;
+                }
+
+                let split = spread.into_inner() * MAX_CHANNEL_SPLIT;
+                let twist = twist.into_inner() * MAX_CHANNEL_TWIST;
+
+                //Multiply and Darken would black out the other two channels of each draw, so
+                //those steps are drawn whole
+                let channels = match blend_mode {
+                    BlendMode::Multiply | BlendMode::Darken => {
+                        vec![(GgColor::new(1.0, 1.0, 1.0, alpha), SNPoint::zero(), 0.0)]
+                    }
+                    _ => vec![
+                        (GgColor::new(1.0, 0.0, 0.0, alpha), *red_offset, twist),
+                        (GgColor::new(0.0, 1.0, 0.0, alpha), SNPoint::zero(), 0.0),
+                        (GgColor::new(0.0, 0.0, 1.0, alpha), *blue_offset, -twist),
+                    ],
+                };
+
+                for &(color, offset, rotation) in &channels {
+                    let split_x = offset.x().into_inner() * split * CONSTS.initial_window_width;
+                    let split_y = offset.y().into_inner() * split * CONSTS.initial_window_height;
+
+                    ggez::graphics::draw(
+                        args.ctx,
+                        &args.history_step().computed_texture,
+                        DrawParam::new()
+                            .color(color)
+                            .offset([0.5, 0.5])
+                            .dest([dest_x + split_x, dest_y + split_y])
+                            .scale([scale_x, scale_y])
+                            .rotation(rotation),
+                    )?;
+                }
+
+                ggez::graphics::set_blend_mode(args.ctx, blend_mode)?;
+            }
             FrameRenderers::None => {}
         }
 
@@ -617,3 +847,67 @@ impl Default for FrameRenderers {
         FrameRenderers::None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_blend_rejects_replace_and_invert() {
+        assert_eq!(FrameBlend::new(BlendMode::Replace, 4).mode, BlendMode::Alpha);
+        assert_eq!(FrameBlend::new(BlendMode::Invert, 4).mode, BlendMode::Alpha);
+        assert_eq!(FrameBlend::new(BlendMode::Add, 4).mode, BlendMode::Add);
+        assert_eq!(FrameBlend::new(BlendMode::Add, 0).interval, FrameBlend::MIN_INTERVAL);
+    }
+
+    #[test]
+    fn mode_at_follows_absolute_time() {
+        let blend = FrameBlend::new(BlendMode::Subtract, 3);
+        let modes: Vec<_> = (0..7).map(|t| blend.mode_at(t, 1)).collect();
+
+        assert_eq!(
+            modes,
+            vec![
+                BlendMode::Subtract,
+                BlendMode::Alpha,
+                BlendMode::Alpha,
+                BlendMode::Subtract,
+                BlendMode::Alpha,
+                BlendMode::Alpha,
+                BlendMode::Subtract,
+            ]
+        );
+
+        //Never more than one non-alpha step among the steps drawn in a single frame
+        let blend = FrameBlend::new(BlendMode::Darken, 2);
+        for t in 0..20 {
+            let darkened = (t..t + 5)
+                .filter(|&t| blend.mode_at(t, 5) == BlendMode::Darken)
+                .count();
+
+            assert!(darkened <= 1);
+        }
+    }
+
+    #[test]
+    fn luminance_clamps_to_edges() {
+        let mut cell_array = Array3::zeros((2, 2, 4));
+        cell_array[[1, 1, 0]] = 255;
+        cell_array[[1, 1, 1]] = 255;
+        cell_array[[1, 1, 2]] = 255;
+
+        assert_eq!(luminance_at(&cell_array, 0.0, 0.0), 0.0);
+        assert!((luminance_at(&cell_array, 1.0, 1.0) - 1.0).abs() < 0.001);
+        assert!((luminance_at(&cell_array, 0.99, 1.5) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn displacement_indices_cover_grid() {
+        let indices = displacement_indices();
+
+        assert_eq!(indices.len(), DISPLACEMENT_GRID * DISPLACEMENT_GRID * 6);
+        assert!(indices
+            .iter()
+            .all(|&i| (i as usize) < (DISPLACEMENT_GRID + 1) * (DISPLACEMENT_GRID + 1)));
+    }
+}
@@ -0,0 +1,220 @@
+use rand::prelude::*;
+
+use crate::prelude::*;
+
+use na::Point2;
+
+const FIELD_SIZE: usize = 96;
+//Parameters are only computed on a coarse grid, since computing them per cell is expensive
+const PARAMETER_GRID_SIZE: usize = 16;
+const STEPS_PER_UPDATE: usize = 8;
+
+//Ranges that keep the simulation in the interesting region of the Pearson classification
+const FEED_RANGE: (f32, f32) = (0.01, 0.1);
+const KILL_RANGE: (f32, f32) = (0.045, 0.07);
+const DIFFUSION_U_RANGE: (f32, f32) = (0.6, 1.0);
+
+//V rarely goes much above 0.4, so scale it up so it covers the whole output range
+const V_OUTPUT_SCALE: f32 = 2.5;
+
+#[derive(Debug, Clone, Copy)]
+pub struct GrayScottParams {
+    pub feed: f32,
+    pub kill: f32,
+    pub diffusion_u: f32,
+    pub diffusion_v: f32,
+}
+
+impl GrayScottParams {
+    pub fn from_unfloats(feed: UNFloat, kill: UNFloat, diffusion: UNFloat) -> Self {
+        let diffusion_u = lerp(
+            DIFFUSION_U_RANGE.0,
+            DIFFUSION_U_RANGE.1,
+            diffusion.into_inner(),
+        );
+
+        Self {
+            feed: lerp(FEED_RANGE.0, FEED_RANGE.1, feed.into_inner()),
+            kill: lerp(KILL_RANGE.0, KILL_RANGE.1, kill.into_inner()),
+            diffusion_u,
+            diffusion_v: diffusion_u * 0.5,
+        }
+    }
+}
+
+/// The persistent state of a two chemical Gray-Scott reaction-diffusion simulation
+#[derive(Debug, Clone)]
+pub struct GrayScottField {
+    width: usize,
+    height: usize,
+    u: Vec<f32>,
+    v: Vec<f32>,
+}
+
+impl GrayScottField {
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut field = Self {
+            width,
+            height,
+            u: vec![1.0; width * height],
+            v: vec![0.0; width * height],
+        };
+
+        field.reseed(&mut thread_rng());
+
+        field
+    }
+
+    /// Drops a few random squares of V into the field to start the reaction
+    pub fn reseed<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let seed_size = (self.width.min(self.height) / 10).max(1);
+
+        for _ in 0..rng.gen_range(1..=8) {
+            let seed_x = rng.gen_range(0..self.width);
+            let seed_y = rng.gen_range(0..self.height);
+
+            for dy in 0..seed_size {
+                for dx in 0..seed_size {
+                    let i = self.index((seed_x + dx) % self.width, (seed_y + dy) % self.height);
+
+                    self.u[i] = 0.5;
+                    self.v[i] = 0.25;
+                }
+            }
+        }
+    }
+
+    /// Advances the simulation, sampling the parameters at a coarse grid of points across the field
+    pub fn update<F: FnMut(SNPoint) -> GrayScottParams>(&mut self, mut get_params: F) {
+        let grid_size = Point2::new(PARAMETER_GRID_SIZE, PARAMETER_GRID_SIZE);
+        let zero = Point2::new(0, 0);
+
+        let params: Vec<_> = (0..PARAMETER_GRID_SIZE * PARAMETER_GRID_SIZE)
+            .map(|i| {
+                get_params(SNPoint::from_usize_range(
+                    Point2::new(i % PARAMETER_GRID_SIZE, i / PARAMETER_GRID_SIZE),
+                    zero,
+                    grid_size,
+                ))
+            })
+            .collect();
+
+        let (width, height) = (self.width, self.height);
+
+        for _ in 0..STEPS_PER_UPDATE {
+            self.step(|x, y| {
+                let grid_x = x * PARAMETER_GRID_SIZE / width;
+                let grid_y = y * PARAMETER_GRID_SIZE / height;
+
+                params[grid_y * PARAMETER_GRID_SIZE + grid_x]
+            });
+        }
+
+        if self.v.iter().all(|v| *v < 0.001) {
+            self.reseed(&mut thread_rng());
+        }
+    }
+
+    fn step<F: Fn(usize, usize) -> GrayScottParams>(&mut self, get_params: F) {
+        let mut next_u = self.u.clone();
+        let mut next_v = self.v.clone();
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = self.index(x, y);
+                let params = get_params(x, y);
+
+                let u = self.u[i];
+                let v = self.v[i];
+                let uvv = u * v * v;
+
+                let (laplace_u, laplace_v) = self.laplacian(x, y);
+
+                next_u[i] = (u + params.diffusion_u * laplace_u - uvv + params.feed * (1.0 - u))
+                    .clamp(0.0, 1.0);
+                next_v[i] = (v + params.diffusion_v * laplace_v + uvv
+                    - (params.feed + params.kill) * v)
+                    .clamp(0.0, 1.0);
+            }
+        }
+
+        self.u = next_u;
+        self.v = next_v;
+    }
+
+    /// 3x3 laplacian with the usual 0.2 adjacent and 0.05 diagonal weights, wrapping at the edges
+    fn laplacian(&self, x: usize, y: usize) -> (f32, f32) {
+        let mut laplace_u = 0.0;
+        let mut laplace_v = 0.0;
+
+        for dy in -1..=1_isize {
+            for dx in -1..=1_isize {
+                let weight = match (dx, dy) {
+                    (0, 0) => -1.0,
+                    (0, _) | (_, 0) => 0.2,
+                    _ => 0.05,
+                };
+
+                let i = self.index(
+                    (x as isize + dx).rem_euclid(self.width as isize) as usize,
+                    (y as isize + dy).rem_euclid(self.height as isize) as usize,
+                );
+
+                laplace_u += self.u[i] * weight;
+                laplace_v += self.v[i] * weight;
+            }
+        }
+
+        (laplace_u, laplace_v)
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    fn point_to_index(&self, point: SNPoint) -> usize {
+        let x = ((point.x().to_unsigned().into_inner() * self.width as f32) as usize)
+            .min(self.width - 1);
+        let y = ((point.y().to_unsigned().into_inner() * self.height as f32) as usize)
+            .min(self.height - 1);
+
+        self.index(x, y)
+    }
+
+    pub fn get_u(&self, point: SNPoint) -> UNFloat {
+        UNFloat::new(self.u[self.point_to_index(point)])
+    }
+
+    /// The concentration of the second chemical, rescaled so it covers the full range
+    pub fn get_v(&self, point: SNPoint) -> UNFloat {
+        UNFloat::new((self.v[self.point_to_index(point)] * V_OUTPUT_SCALE).min(1.0))
+    }
+}
+
+impl Default for GrayScottField {
+    fn default() -> Self {
+        Self::new(FIELD_SIZE, FIELD_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stays_in_range() {
+        let mut field = GrayScottField::new(32, 32);
+
+        for _ in 0..10 {
+            field.update(|_| {
+                GrayScottParams::from_unfloats(UNFloat::ONE, UNFloat::ZERO, UNFloat::ONE)
+            });
+        }
+
+        assert!(field
+            .u
+            .iter()
+            .chain(field.v.iter())
+            .all(|c| (0.0..=1.0).contains(c)));
+    }
+}
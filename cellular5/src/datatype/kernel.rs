@@ -0,0 +1,287 @@
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+pub type Kernel3x3 = Kernel<3>;
+pub type Kernel5x5 = Kernel<5>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernelPreset {
+    SobelX,
+    SobelY,
+    Laplacian,
+    Gaussian,
+    Emboss,
+    Free,
+}
+
+impl KernelPreset {
+    const ALL: [KernelPreset; 6] = [
+        KernelPreset::SobelX,
+        KernelPreset::SobelY,
+        KernelPreset::Laplacian,
+        KernelPreset::Gaussian,
+        KernelPreset::Emboss,
+        KernelPreset::Free,
+    ];
+}
+
+/// How small a kernel's total can be relative to the sum of its absolute weights before it counts
+/// as zero sum
+const ZERO_SUM_RATIO: f32 = 0.25;
+
+/// A square convolution kernel with odd side length SIZE, stored row major
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Kernel<const SIZE: usize> {
+    preset: KernelPreset,
+    weights: Vec<f32>,
+}
+
+impl<const SIZE: usize> Kernel<SIZE> {
+    const CENTER: isize = SIZE as isize / 2;
+
+    pub fn from_preset<R: Rng + ?Sized>(preset: KernelPreset, rng: &mut R) -> Self {
+        let smooth = binomial_row(SIZE);
+        let derivative = derivative_row(SIZE);
+        let center = Self::CENTER;
+
+        let weights = (0..SIZE * SIZE)
+            .map(|i| {
+                let (x, y) = (i % SIZE, i / SIZE);
+                let (dx, dy) = (x as isize - center, y as isize - center);
+
+                match preset {
+                    KernelPreset::SobelX => smooth[y] * derivative[x],
+                    KernelPreset::SobelY => smooth[x] * derivative[y],
+                    KernelPreset::Laplacian => {
+                        if (dx, dy) == (0, 0) {
+                            (SIZE * SIZE - 1) as f32
+                        } else {
+                            -1.0
+                        }
+                    }
+                    KernelPreset::Gaussian => smooth[x] * smooth[y],
+                    KernelPreset::Emboss => {
+                        if (dx, dy) == (0, 0) {
+                            1.0
+                        } else {
+                            (dx + dy).signum() as f32
+                        }
+                    }
+                    KernelPreset::Free => rng.gen_range(-1.0..=1.0),
+                }
+            })
+            .collect();
+
+        let mut kernel = Self { preset, weights };
+        kernel.normalise();
+        kernel
+    }
+
+    pub fn preset(&self) -> KernelPreset {
+        self.preset
+    }
+
+    /// Kernels whose weights nearly cancel out are treated as zero sum, since dividing by a tiny
+    /// total would blow the weights up
+    fn is_zero_sum(&self) -> bool {
+        let total: f32 = self.weights.iter().sum();
+        let magnitude: f32 = self.weights.iter().map(|w| w.abs()).sum();
+
+        total.abs() <= magnitude * ZERO_SUM_RATIO
+    }
+
+    /// Scales the weights so a kernel can't push a channel further than the full range
+    fn normalise(&mut self) {
+        let divisor = if self.is_zero_sum() {
+            self.weights.iter().map(|w| w.abs()).sum::<f32>() * 0.5
+        } else {
+            self.weights.iter().sum::<f32>().abs()
+        };
+
+        if divisor > 0.0 {
+            for weight in self.weights.iter_mut() {
+                *weight /= divisor;
+            }
+        }
+    }
+
+    /// Nudges a single weight, which turns any preset into a free kernel
+    fn nudge_weight<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let weight = self.weights.choose_mut(rng).unwrap();
+        *weight = (*weight + rng.gen_range(-0.25..=0.25)).clamp(-1.0, 1.0);
+
+        self.preset = KernelPreset::Free;
+        self.normalise();
+    }
+
+    /// Kernels that sum to zero only respond to differences, so we offset them to mid gray
+    fn bias(&self) -> f32 {
+        if self.is_zero_sum() {
+            0.5
+        } else {
+            0.0
+        }
+    }
+
+    /// Iterates over (dx, dy, weight) for each cell in the kernel
+    pub fn offsets(&self) -> impl Iterator<Item = (isize, isize, f32)> + '_ {
+        self.weights.iter().enumerate().map(|(i, weight)| {
+            (
+                (i % SIZE) as isize - Self::CENTER,
+                (i / SIZE) as isize - Self::CENTER,
+                *weight,
+            )
+        })
+    }
+
+    /// Convolves the previous history step around the current coordinates
+    pub fn convolve_color(&self, compute_arg: &ComArg) -> FloatColor {
+        let (x, y) = compute_arg.history_coords();
+
+        let mut sum = [self.bias(); 3];
+
+        for (dx, dy, weight) in self.offsets() {
+            let color = compute_arg.get_previous_color(x + dx, y + dy);
+
+            sum[0] += color.r.into_inner() * weight;
+            sum[1] += color.g.into_inner() * weight;
+            sum[2] += color.b.into_inner() * weight;
+        }
+
+        FloatColor {
+            r: UNFloat::new(sum[0].clamp(0.0, 1.0)),
+            g: UNFloat::new(sum[1].clamp(0.0, 1.0)),
+            b: UNFloat::new(sum[2].clamp(0.0, 1.0)),
+            a: UNFloat::ONE,
+        }
+    }
+
+    /// The gradient magnitude of the previous history step's brightness, using Sobel kernels of
+    /// this size. Only derivative kernels give a meaningful gradient, so this doesn't depend on
+    /// any evolved weights.
+    pub fn edge_magnitude(compute_arg: &ComArg) -> UNFloat {
+        let (x, y) = compute_arg.history_coords();
+
+        let smooth = binomial_row(SIZE);
+        let derivative = derivative_row(SIZE);
+        //The same scale normalise gives zero sum kernels
+        let divisor = smooth.iter().sum::<f32>()
+            * derivative.iter().map(|w| w.abs()).sum::<f32>()
+            * 0.5;
+
+        let mut gradient_x = 0.0;
+        let mut gradient_y = 0.0;
+
+        for i in 0..SIZE * SIZE {
+            let (kx, ky) = (i % SIZE, i / SIZE);
+            let brightness = compute_arg
+                .get_previous_color(x + kx as isize - Self::CENTER, y + ky as isize - Self::CENTER)
+                .get_average();
+
+            gradient_x += smooth[ky] * derivative[kx] * brightness;
+            gradient_y += smooth[kx] * derivative[ky] * brightness;
+        }
+
+        UNFloat::new(
+            ((gradient_x * gradient_x + gradient_y * gradient_y).sqrt() / divisor).min(1.0),
+        )
+    }
+}
+
+/// A row of Pascal's triangle, used as a smoothing filter
+fn binomial_row(size: usize) -> Vec<f32> {
+    (0..size).fold(vec![1.0], |row, _| {
+        if row.len() == size {
+            row
+        } else {
+            let mut next = vec![1.0; row.len() + 1];
+            for i in 1..row.len() {
+                next[i] = row[i - 1] + row[i];
+            }
+            next
+        }
+    })
+}
+
+/// Differences between the binomial row one size smaller, e.g. [-1, 0, 1] or [-1, -2, 0, 2, 1]
+fn derivative_row(size: usize) -> Vec<f32> {
+    let smooth = binomial_row(size - 1);
+
+    (0..size)
+        .map(|i| {
+            let left = if i > 0 { smooth[i - 1] } else { 0.0 };
+            let right = if i < size - 1 { smooth[i] } else { 0.0 };
+
+            left - right
+        })
+        .collect()
+}
+
+impl<'a, const SIZE: usize> Generatable<'a> for Kernel<SIZE> {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        let preset = *KernelPreset::ALL.choose(rng).unwrap();
+        Self::from_preset(preset, rng)
+    }
+}
+
+impl<'a, const SIZE: usize> Mutatable<'a> for Kernel<SIZE> {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        if rng.gen_bool(0.5) {
+            *self = Self::generate_rng(rng, arg.into());
+        } else {
+            self.nudge_weight(rng);
+        }
+    }
+}
+
+impl<'a, const SIZE: usize> Updatable<'a> for Kernel<SIZE> {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a, const SIZE: usize> UpdatableRecursively<'a> for Kernel<SIZE> {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_rows() {
+        assert_eq!(binomial_row(3), vec![1.0, 2.0, 1.0]);
+        assert_eq!(binomial_row(5), vec![1.0, 4.0, 6.0, 4.0, 1.0]);
+        assert_eq!(derivative_row(3), vec![-1.0, 0.0, 1.0]);
+        assert_eq!(derivative_row(5), vec![-1.0, -2.0, 0.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn mutation_keeps_weights_bounded() {
+        let mut rng = thread_rng();
+        let mut kernel = Kernel3x3::from_preset(KernelPreset::Gaussian, &mut rng);
+
+        for _ in 0..1000 {
+            kernel.nudge_weight(&mut rng);
+            assert_eq!(kernel.preset, KernelPreset::Free);
+
+            let magnitude: f32 = kernel.weights.iter().map(|w| w.abs()).sum();
+            assert!(magnitude <= 1.0 / ZERO_SUM_RATIO + 0.001, "{:?}", kernel.weights);
+        }
+    }
+
+    #[test]
+    fn gaussian_sums_to_one() {
+        let kernel = Kernel5x5::from_preset(KernelPreset::Gaussian, &mut thread_rng());
+        let total: f32 = kernel.offsets().map(|(_, _, w)| w).sum();
+
+        assert!((total - 1.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,127 @@
+use lazy_static::lazy_static;
+
+use crate::prelude::*;
+
+const MIN_RADIUS: usize = 2;
+const MAX_RADIUS: usize = 7;
+
+lazy_static! {
+    /// Normalised ring kernels for each radius, as (dx, dy, weight)
+    static ref RING_KERNELS: Vec<Vec<(isize, isize, f32)>> =
+        (0..=MAX_RADIUS).map(ring_kernel).collect();
+}
+
+/// Smooth bump peaking halfway between the center and the edge of the kernel, as used by Lenia
+fn ring_kernel(radius: usize) -> Vec<(isize, isize, f32)> {
+    let r = radius as isize;
+
+    let mut kernel: Vec<_> = (-r..=r)
+        .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+        .filter_map(|(dx, dy)| {
+            let distance = ((dx * dx + dy * dy) as f32).sqrt() / radius.max(1) as f32;
+
+            if distance > 0.0 && distance < 1.0 {
+                Some((dx, dy, (4.0 - 1.0 / (distance * (1.0 - distance))).exp()))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let total: f32 = kernel.iter().map(|(_, _, weight)| weight).sum();
+
+    if total > 0.0 {
+        for (_, _, weight) in kernel.iter_mut() {
+            *weight /= total;
+        }
+    }
+
+    kernel
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LeniaParams {
+    pub radius: usize,
+    pub mu: f32,
+    pub sigma: f32,
+    pub dt: f32,
+}
+
+impl LeniaParams {
+    pub fn new(radius: Nibble, mu: UNFloat, sigma: UNFloat, dt: UNFloat) -> Self {
+        Self {
+            radius: MIN_RADIUS + radius.into_inner() as usize % (MAX_RADIUS - MIN_RADIUS + 1),
+            mu: lerp(0.05, 0.5, mu.into_inner()),
+            sigma: lerp(0.005, 0.1, sigma.into_inner()),
+            dt: lerp(0.05, 0.5, dt.into_inner()),
+        }
+    }
+
+    /// Gaussian growth mapping the kernel potential to -1..1
+    pub fn growth(&self, potential: f32) -> f32 {
+        2.0 * (-(potential - self.mu).powi(2) / (2.0 * self.sigma.powi(2))).exp() - 1.0
+    }
+
+    pub fn kernel(&self) -> &'static [(isize, isize, f32)] {
+        &RING_KERNELS[self.radius]
+    }
+
+    pub fn next_state(&self, current: f32, potential: f32) -> UNFloat {
+        UNFloat::new((current + self.dt * self.growth(potential)).clamp(0.0, 1.0))
+    }
+}
+
+/// Steps each channel of the cell independently, using the previous history step as the state
+pub fn lenia_step_color(compute_arg: &ComArg, params: &LeniaParams) -> FloatColor {
+    let (x, y) = compute_arg.history_coords();
+
+    let mut potential = [0.0; 3];
+
+    for (dx, dy, weight) in params.kernel() {
+        let color = compute_arg.get_previous_color(x + dx, y + dy);
+
+        potential[0] += color.r.into_inner() * weight;
+        potential[1] += color.g.into_inner() * weight;
+        potential[2] += color.b.into_inner() * weight;
+    }
+
+    let current = compute_arg.get_previous_color(x, y);
+
+    FloatColor {
+        r: params.next_state(current.r.into_inner(), potential[0]),
+        g: params.next_state(current.g.into_inner(), potential[1]),
+        b: params.next_state(current.b.into_inner(), potential[2]),
+        a: UNFloat::ONE,
+    }
+}
+
+/// Steps the cell using the average of the previous history step's colors as the state
+pub fn lenia_step_unfloat(compute_arg: &ComArg, params: &LeniaParams) -> UNFloat {
+    let (x, y) = compute_arg.history_coords();
+
+    let potential: f32 = params
+        .kernel()
+        .iter()
+        .map(|(dx, dy, weight)| {
+            compute_arg.get_previous_color(x + dx, y + dy).get_average() * weight
+        })
+        .sum();
+
+    params.next_state(
+        compute_arg.get_previous_color(x, y).get_average(),
+        potential,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernels_are_normalised() {
+        for radius in MIN_RADIUS..=MAX_RADIUS {
+            let total: f32 = RING_KERNELS[radius].iter().map(|(_, _, w)| w).sum();
+            assert!((total - 1.0).abs() < 0.001);
+        }
+    }
+}
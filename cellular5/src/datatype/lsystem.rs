@@ -0,0 +1,296 @@
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use na::{Point2, Vector2};
+
+const MAX_ITERATIONS: usize = 6;
+//Expansion stops early once the string gets this long, so line drawing stays cheap
+const MAX_SYMBOLS: usize = 8192;
+//Drawing is scaled to fit inside this margin
+const DRAWING_EXTENT: f32 = 0.9;
+
+const PRESETS: &[(&str, &[(char, &str)])] = &[
+    // Koch curve
+    ("F", &[('F', "F+F-F-F+F")]),
+    // Fractal plant
+    ("X", &[('X', "F+[[X]-X]-F[-FX]+X"), ('F', "FF")]),
+    // Dragon curve
+    ("FX", &[('X', "X+YF+"), ('Y', "-FX-Y")]),
+    // Bush
+    ("F", &[('F', "FF+[+F-F-F]-[-F+F+F]")]),
+    // Sierpinski arrowhead
+    ("A", &[('A', "B-A-B"), ('B', "A+B+A")]),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LSystemRule {
+    pub predecessor: char,
+    pub successor: String,
+}
+
+/// An L-system grammar, interpreted with turtle commands: F, A and B draw forward, f moves
+/// forward without drawing, + and - turn, and [ and ] push and pop the turtle's state
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(from = "LSystemData")]
+pub struct LSystem {
+    axiom: String,
+    rules: Vec<LSystemRule>,
+    iterations: usize,
+    #[serde(skip)]
+    expanded: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LSystemData {
+    axiom: String,
+    rules: Vec<LSystemRule>,
+    iterations: usize,
+}
+
+impl From<LSystemData> for LSystem {
+    /// Goes through new so a hand edited genome can't ask for an unbounded number of iterations
+    fn from(data: LSystemData) -> Self {
+        Self::new(&data.axiom, data.rules, data.iterations)
+    }
+}
+
+impl LSystem {
+    pub fn new(axiom: &str, rules: Vec<LSystemRule>, iterations: usize) -> Self {
+        Self {
+            axiom: axiom.to_owned(),
+            rules,
+            iterations: iterations.min(MAX_ITERATIONS),
+            expanded: None,
+        }
+    }
+
+    fn from_preset<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let (axiom, rules) = PRESETS.choose(rng).unwrap();
+
+        Self::new(
+            axiom,
+            rules
+                .iter()
+                .map(|(predecessor, successor)| LSystemRule {
+                    predecessor: *predecessor,
+                    successor: (*successor).to_owned(),
+                })
+                .collect(),
+            rng.gen_range(2..=MAX_ITERATIONS),
+        )
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::new(
+            "F",
+            vec![LSystemRule {
+                predecessor: 'F',
+                successor: random_successor(rng, 2),
+            }],
+            rng.gen_range(2..=MAX_ITERATIONS),
+        )
+    }
+
+    /// The string after rewriting, cached since the grammar only changes on mutation
+    pub fn expanded(&mut self) -> &str {
+        if self.expanded.is_none() {
+            self.expanded = Some(self.expand());
+        }
+
+        self.expanded.as_ref().unwrap()
+    }
+
+    fn expand(&self) -> String {
+        let mut current = self.axiom.clone();
+
+        for _ in 0..self.iterations {
+            let mut next = String::with_capacity(current.len() * 2);
+
+            for symbol in current.chars() {
+                match self.rules.iter().find(|rule| rule.predecessor == symbol) {
+                    Some(rule) => next.push_str(&rule.successor),
+                    None => next.push(symbol),
+                }
+            }
+
+            if next.len() > MAX_SYMBOLS {
+                break;
+            }
+
+            current = next;
+        }
+
+        current
+    }
+}
+
+/// A random run of turtle commands with balanced brackets
+fn random_successor<R: Rng + ?Sized>(rng: &mut R, max_depth: usize) -> String {
+    let mut successor = String::new();
+
+    for _ in 0..rng.gen_range(2..=6) {
+        match rng.gen_range(0..5) {
+            0 | 1 => successor.push('F'),
+            2 => successor.push('+'),
+            3 => successor.push('-'),
+            _ if max_depth > 0 => {
+                successor.push('[');
+                successor.push_str(&random_successor(rng, max_depth - 1));
+                successor.push(']');
+            }
+            _ => successor.push('F'),
+        }
+    }
+
+    //Without an F the grammar never grows
+    if !successor.contains('F') {
+        successor.push('F');
+    }
+
+    successor
+}
+
+/// Walks the turtle over the commands, returning the lines it draws scaled to fit the buffer
+pub fn turtle_lines(commands: &str, turn_angle: Angle) -> Vec<(SNPoint, SNPoint)> {
+    let turn = turn_angle.into_inner();
+
+    let mut position = Point2::new(0.0_f32, 0.0);
+    let mut heading = 0.0_f32;
+    let mut stack = Vec::new();
+    let mut lines = Vec::new();
+
+    for command in commands.chars() {
+        match command {
+            'F' | 'A' | 'B' | 'f' => {
+                let next = position + Vector2::new(heading.sin(), -heading.cos());
+
+                if command != 'f' {
+                    lines.push((position, next));
+                }
+
+                position = next;
+            }
+            '+' => heading += turn,
+            '-' => heading -= turn,
+            '[' => stack.push((position, heading)),
+            ']' => {
+                if let Some((saved_position, saved_heading)) = stack.pop() {
+                    position = saved_position;
+                    heading = saved_heading;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (min, max) = lines.iter().fold(
+        (
+            Point2::new(f32::MAX, f32::MAX),
+            Point2::new(f32::MIN, f32::MIN),
+        ),
+        |(min, max), (a, b)| {
+            (
+                Point2::new(min.x.min(a.x).min(b.x), min.y.min(a.y).min(b.y)),
+                Point2::new(max.x.max(a.x).max(b.x), max.y.max(a.y).max(b.y)),
+            )
+        },
+    );
+
+    let center = Point2::from((min.coords + max.coords) * 0.5);
+    let size = (max.x - min.x).max(max.y - min.y).max(f32::EPSILON);
+    let scale = 2.0 * DRAWING_EXTENT / size;
+
+    let to_snpoint = |p: Point2<f32>| SNPoint::new(Point2::from((p - center) * scale));
+
+    lines
+        .into_iter()
+        .map(|(a, b)| (to_snpoint(a), to_snpoint(b)))
+        .collect()
+}
+
+impl<'a> Generatable<'a> for LSystem {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        if rng.gen_bool(0.5) {
+            Self::from_preset(rng)
+        } else {
+            Self::random(rng)
+        }
+    }
+}
+
+impl<'a> Mutatable<'a> for LSystem {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        match rng.gen_range(0..3) {
+            0 => *self = Self::generate_rng(rng, arg.into()),
+            1 => {
+                self.iterations = rng.gen_range(1..=MAX_ITERATIONS);
+                self.expanded = None;
+            }
+            //A grammar loaded without any rules has none to tweak, so it's replaced outright
+            _ if self.rules.is_empty() => *self = Self::generate_rng(rng, arg.into()),
+            _ => {
+                let rule = self.rules.choose_mut(rng).unwrap();
+                rule.successor = random_successor(rng, 2);
+                self.expanded = None;
+            }
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for LSystem {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a> UpdatableRecursively<'a> for LSystem {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expansion_is_bounded() {
+        let mut lsystem = LSystem::new(
+            "F",
+            vec![LSystemRule {
+                predecessor: 'F',
+                successor: "FFFFFFFF".to_owned(),
+            }],
+            MAX_ITERATIONS,
+        );
+
+        assert_eq!(lsystem.expanded().len(), 4096);
+    }
+
+    #[test]
+    fn lines_fit_buffer() {
+        let lines = turtle_lines("F+F-F[+FF]F", Angle::new(1.0));
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines.iter().all(|(a, b)| {
+            [a, b].iter().all(|p| {
+                p.x().into_inner().abs() <= DRAWING_EXTENT + 0.001
+                    && p.y().into_inner().abs() <= DRAWING_EXTENT + 0.001
+            })
+        }));
+    }
+
+    #[test]
+    fn deserialized_iterations_are_clamped() {
+        let lsystem: LSystem =
+            serde_yaml::from_str("axiom: F\nrules: []\niterations: 1000").unwrap();
+
+        assert_eq!(lsystem.iterations, MAX_ITERATIONS);
+        assert!(lsystem.rules.is_empty());
+    }
+}
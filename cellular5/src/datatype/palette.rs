@@ -0,0 +1,203 @@
+use std::f32::consts::PI;
+
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+const MIN_STOPS: usize = 2;
+const MAX_STOPS: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HarmonyRule {
+    Monochromatic,
+    Analogous,
+    Complementary,
+    SplitComplementary,
+    Triadic,
+    Tetradic,
+}
+
+impl HarmonyRule {
+    const ALL: [HarmonyRule; 6] = [
+        HarmonyRule::Monochromatic,
+        HarmonyRule::Analogous,
+        HarmonyRule::Complementary,
+        HarmonyRule::SplitComplementary,
+        HarmonyRule::Triadic,
+        HarmonyRule::Tetradic,
+    ];
+
+    /// Hue offsets from the base hue, in turns
+    fn hue_offsets(self) -> &'static [f32] {
+        match self {
+            HarmonyRule::Monochromatic => &[0.0],
+            HarmonyRule::Analogous => &[-1.0 / 12.0, 0.0, 1.0 / 12.0],
+            HarmonyRule::Complementary => &[0.0, 0.5],
+            HarmonyRule::SplitComplementary => &[0.0, 5.0 / 12.0, 7.0 / 12.0],
+            HarmonyRule::Triadic => &[0.0, 1.0 / 3.0, 2.0 / 3.0],
+            HarmonyRule::Tetradic => &[0.0, 0.25, 0.5, 0.75],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteSpace {
+    HSV,
+    LAB,
+}
+
+/// A gradient of colours picked by a harmony rule. Stops step through the rule's hues while
+/// ramping from dark to light, so mapping a scalar through it keeps the scalar's shape readable.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Palette {
+    rule: HarmonyRule,
+    space: PaletteSpace,
+    //In turns
+    base_hue: f32,
+    saturation: f32,
+    stop_count: usize,
+    stops: Vec<FloatColor>,
+}
+
+impl Palette {
+    pub fn new(
+        rule: HarmonyRule,
+        space: PaletteSpace,
+        base_hue: f32,
+        saturation: f32,
+        stop_count: usize,
+    ) -> Self {
+        let mut palette = Self {
+            rule,
+            space,
+            base_hue: base_hue.rem_euclid(1.0),
+            saturation: saturation.clamp(0.0, 1.0),
+            stop_count: stop_count.clamp(MIN_STOPS, MAX_STOPS),
+            stops: Vec::new(),
+        };
+
+        palette.rebuild();
+
+        palette
+    }
+
+    fn rebuild(&mut self) {
+        let offsets = self.rule.hue_offsets();
+
+        self.stops = (0..self.stop_count)
+            .map(|i| {
+                let ratio = i as f32 / (self.stop_count - 1) as f32;
+                let hue = (self.base_hue + offsets[i % offsets.len()]).rem_euclid(1.0);
+
+                match self.space {
+                    PaletteSpace::HSV => HSVColor {
+                        h: Angle::new(hue * 2.0 * PI),
+                        s: UNFloat::new(self.saturation),
+                        v: UNFloat::new(lerp(0.15, 1.0, ratio)),
+                        a: UNFloat::ONE,
+                    }
+                    .into(),
+                    PaletteSpace::LAB => {
+                        let chroma = self.saturation * 0.6;
+
+                        LABColor {
+                            l: SNFloat::new(lerp(-0.8, 0.8, ratio)),
+                            ab: SNComplex::from_snfloats(
+                                SNFloat::new(chroma * (hue * 2.0 * PI).cos()),
+                                SNFloat::new(chroma * (hue * 2.0 * PI).sin()),
+                            ),
+                            alpha: UNFloat::ONE,
+                        }
+                        .into()
+                    }
+                }
+            })
+            .collect();
+    }
+
+    pub fn stops(&self) -> &[FloatColor] {
+        &self.stops
+    }
+
+    pub fn sample(&self, t: UNFloat) -> FloatColor {
+        let position = t.into_inner() * (self.stops.len() - 1) as f32;
+        let index = (position as usize).min(self.stops.len() - 2);
+
+        self.stops[index].lerp(
+            self.stops[index + 1],
+            UNFloat::new((position - index as f32).clamp(0.0, 1.0)),
+        )
+    }
+}
+
+impl<'a> Generatable<'a> for Palette {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        Self::new(
+            *HarmonyRule::ALL.choose(rng).unwrap(),
+            if rng.gen() {
+                PaletteSpace::HSV
+            } else {
+                PaletteSpace::LAB
+            },
+            rng.gen(),
+            rng.gen_range(0.4..=1.0),
+            rng.gen_range(MIN_STOPS..=MAX_STOPS),
+        )
+    }
+}
+
+impl<'a> Mutatable<'a> for Palette {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        match rng.gen_range(0..4) {
+            0 => *self = Self::generate_rng(rng, arg.into()),
+            1 => {
+                self.base_hue = (self.base_hue + rng.gen_range(-0.1..=0.1)).rem_euclid(1.0);
+                self.rebuild();
+            }
+            2 => {
+                self.rule = *HarmonyRule::ALL.choose(rng).unwrap();
+                self.rebuild();
+            }
+            _ => {
+                self.stop_count = rng.gen_range(MIN_STOPS..=MAX_STOPS);
+                self.rebuild();
+            }
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for Palette {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a> UpdatableRecursively<'a> for Palette {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_hit_the_ends() {
+        let palette = Palette::new(HarmonyRule::Triadic, PaletteSpace::HSV, 0.3, 0.8, 5);
+
+        let close = |a: FloatColor, b: FloatColor| {
+            (a.r.into_inner() - b.r.into_inner()).abs() < 0.001
+                && (a.g.into_inner() - b.g.into_inner()).abs() < 0.001
+                && (a.b.into_inner() - b.b.into_inner()).abs() < 0.001
+        };
+
+        assert_eq!(palette.stops().len(), 5);
+        assert!(close(palette.sample(UNFloat::ZERO), palette.stops()[0]));
+        assert!(close(palette.sample(UNFloat::ONE), palette.stops()[4]));
+    }
+}
@@ -0,0 +1,167 @@
+use std::f32::consts::PI;
+
+use rand::prelude::*;
+
+use crate::prelude::*;
+
+const FIELD_SIZE: usize = 128;
+const AGENT_COUNT: usize = 4096;
+const DEPOSIT: f32 = 0.5;
+
+//Ranges that keep the network from either dissolving or saturating the whole field
+const SENSOR_DISTANCE_RANGE: (f32, f32) = (1.0, 12.0);
+const DECAY_RANGE: (f32, f32) = (0.02, 0.25);
+
+#[derive(Debug, Clone, Copy)]
+pub struct PhysarumParams {
+    pub sensor_angle: f32,
+    pub sensor_distance: f32,
+    pub decay: f32,
+}
+
+impl PhysarumParams {
+    pub fn new(sensor_angle: Angle, sensor_distance: UNFloat, decay: UNFloat) -> Self {
+        Self {
+            //Sensors pointing backwards just make agents spin on the spot
+            sensor_angle: sensor_angle.into_inner().abs() % PI,
+            sensor_distance: lerp(
+                SENSOR_DISTANCE_RANGE.0,
+                SENSOR_DISTANCE_RANGE.1,
+                sensor_distance.into_inner(),
+            ),
+            decay: lerp(DECAY_RANGE.0, DECAY_RANGE.1, decay.into_inner()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PhysarumAgent {
+    x: f32,
+    y: f32,
+    heading: f32,
+}
+
+/// A slime mould simulation: agents follow the trail left by each other, which spreads and fades
+#[derive(Debug, Clone)]
+pub struct PhysarumField {
+    width: usize,
+    height: usize,
+    trail: Vec<f32>,
+    agents: Vec<PhysarumAgent>,
+}
+
+impl PhysarumField {
+    pub fn new(width: usize, height: usize, agent_count: usize) -> Self {
+        let mut rng = thread_rng();
+
+        Self {
+            width,
+            height,
+            trail: vec![0.0; width * height],
+            agents: (0..agent_count)
+                .map(|_| PhysarumAgent {
+                    x: rng.gen_range(0.0..width as f32),
+                    y: rng.gen_range(0.0..height as f32),
+                    heading: rng.gen_range(0.0..2.0 * PI),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn update(&mut self, params: &PhysarumParams) {
+        let mut rng = thread_rng();
+
+        for i in 0..self.agents.len() {
+            let agent = self.agents[i];
+
+            let ahead = self.sense(&agent, 0.0, params);
+            let left = self.sense(&agent, -params.sensor_angle, params);
+            let right = self.sense(&agent, params.sensor_angle, params);
+
+            let heading = if ahead >= left && ahead >= right {
+                agent.heading
+            } else if ahead < left && ahead < right {
+                agent.heading + params.sensor_angle * if rng.gen() { 1.0 } else { -1.0 }
+            } else if left > right {
+                agent.heading - params.sensor_angle
+            } else {
+                agent.heading + params.sensor_angle
+            };
+
+            let x = (agent.x + heading.cos()).rem_euclid(self.width as f32);
+            let y = (agent.y + heading.sin()).rem_euclid(self.height as f32);
+
+            self.agents[i] = PhysarumAgent { x, y, heading };
+
+            let index = self.index(x as isize, y as isize);
+            self.trail[index] = (self.trail[index] + DEPOSIT).min(1.0);
+        }
+
+        self.diffuse(params.decay);
+    }
+
+    fn sense(&self, agent: &PhysarumAgent, offset: f32, params: &PhysarumParams) -> f32 {
+        let angle = agent.heading + offset;
+
+        self.trail[self.index(
+            (agent.x + angle.cos() * params.sensor_distance) as isize,
+            (agent.y + angle.sin() * params.sensor_distance) as isize,
+        )]
+    }
+
+    /// 3x3 mean blur followed by decay, wrapping at the edges
+    fn diffuse(&mut self, decay: f32) {
+        let mut next = vec![0.0; self.trail.len()];
+
+        for y in 0..self.height as isize {
+            for x in 0..self.width as isize {
+                let mut sum = 0.0;
+
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        sum += self.trail[self.index(x + dx, y + dy)];
+                    }
+                }
+
+                next[self.index(x, y)] = sum / 9.0 * (1.0 - decay);
+            }
+        }
+
+        self.trail = next;
+    }
+
+    fn index(&self, x: isize, y: isize) -> usize {
+        y.rem_euclid(self.height as isize) as usize * self.width
+            + x.rem_euclid(self.width as isize) as usize
+    }
+
+    pub fn get(&self, point: SNPoint) -> UNFloat {
+        let x = point.x().to_unsigned().into_inner() * self.width as f32;
+        let y = point.y().to_unsigned().into_inner() * self.height as f32;
+
+        UNFloat::new(self.trail[self.index(x as isize, y as isize)])
+    }
+}
+
+impl Default for PhysarumField {
+    fn default() -> Self {
+        Self::new(FIELD_SIZE, FIELD_SIZE, AGENT_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trail_stays_in_range() {
+        let mut field = PhysarumField::new(32, 32, 256);
+        let params = PhysarumParams::new(Angle::new(PI / 4.0), UNFloat::ONE, UNFloat::ZERO);
+
+        for _ in 0..10 {
+            field.update(&params);
+        }
+
+        assert!(field.trail.iter().all(|t| (0.0..=1.0).contains(t)));
+    }
+}
@@ -0,0 +1,124 @@
+use std::f32::consts::PI;
+
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use na::Point2;
+
+/// A point in polar form, measured from the origin. Theta is taken clockwise from the positive
+/// y axis, matching how the line buffers have always stepped, and rho is capped at 1 so the
+/// point always fits inside the unit square.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SNPolar {
+    theta: Angle,
+    rho: UNFloat,
+}
+
+impl SNPolar {
+    pub const ZERO: Self = Self {
+        theta: Angle::ZERO,
+        rho: UNFloat::ZERO,
+    };
+
+    pub fn new(theta: Angle, rho: UNFloat) -> Self {
+        Self { theta, rho }
+    }
+
+    pub fn from_snpoint(point: SNPoint) -> Self {
+        let p = point.into_inner();
+
+        Self {
+            theta: Angle::new(f32::atan2(p.x, p.y)),
+            rho: UNFloat::new_clamped(p.coords.norm()),
+        }
+    }
+
+    pub fn to_snpoint(self) -> SNPoint {
+        let theta = self.theta.into_inner();
+        let rho = self.rho.into_inner();
+
+        SNPoint::new(Point2::new(rho * theta.sin(), rho * theta.cos()))
+    }
+
+    pub fn theta(self) -> Angle {
+        self.theta
+    }
+
+    pub fn rho(self) -> UNFloat {
+        self.rho
+    }
+
+    pub fn rotate(self, angle: Angle) -> Self {
+        Self {
+            theta: self.theta + angle,
+            rho: self.rho,
+        }
+    }
+
+    pub fn scale_rho(self, scale: UNFloat) -> Self {
+        Self {
+            theta: self.theta,
+            rho: UNFloat::new(self.rho.into_inner() * scale.into_inner()),
+        }
+    }
+
+    /// Archimedean spiral out from the origin, where t = 1 is reached after the given turns
+    pub fn spiral(t: UNFloat, turns: f32) -> Self {
+        Self {
+            theta: Angle::new(t.into_inner() * turns * 2.0 * PI),
+            rho: t,
+        }
+    }
+}
+
+impl<'a> Generatable<'a> for SNPolar {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        Self::new(
+            Angle::new(rng.gen_range(0.0..2.0 * PI)),
+            UNFloat::new(rng.gen_range(0.0..=1.0)),
+        )
+    }
+}
+
+impl<'a> Mutatable<'a> for SNPolar {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        match rng.gen_range(0..3) {
+            0 => *self = Self::generate_rng(rng, arg.into()),
+            1 => self.theta = self.theta + Angle::new(rng.gen_range(-0.25 * PI..=0.25 * PI)),
+            _ => {
+                self.rho = UNFloat::new_clamped(self.rho.into_inner() + rng.gen_range(-0.1..=0.1))
+            }
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for SNPolar {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a> UpdatableRecursively<'a> for SNPolar {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snpoint_round_trip() {
+        let polar = SNPolar::new(Angle::new(1.0), UNFloat::new(0.5));
+        let round_trip = SNPolar::from_snpoint(polar.to_snpoint());
+
+        assert!((round_trip.theta().into_inner() - 1.0).abs() < 0.001);
+        assert!((round_trip.rho().into_inner() - 0.5).abs() < 0.001);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::prelude::*;
+
+use na::{Point2, Vector2};
+
+/// Distance from a shape's boundary, negative inside the shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignedDistance(f32);
+
+impl SignedDistance {
+    pub fn new(value: f32) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> f32 {
+        self.0
+    }
+
+    pub fn is_inside(self) -> bool {
+        self.0 <= 0.0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0.min(other.0))
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
+    }
+
+    /// Cuts other out of self
+    pub fn subtraction(self, other: Self) -> Self {
+        Self(self.0.max(-other.0))
+    }
+
+    /// Polynomial smooth minimum, blending the shapes together within smoothing of the seam
+    pub fn smooth_union(self, other: Self, smoothing: f32) -> Self {
+        if smoothing <= 0.0 {
+            return self.union(other);
+        }
+
+        let h = (0.5 + 0.5 * (other.0 - self.0) / smoothing).clamp(0.0, 1.0);
+
+        Self(lerp(other.0, self.0, h) - smoothing * h * (1.0 - h))
+    }
+}
+
+pub fn sd_circle(p: Point2<f32>, center: Point2<f32>, radius: f32) -> SignedDistance {
+    SignedDistance((p - center).norm() - radius)
+}
+
+/// The sign of half_size is ignored, so any point can be used as a size
+pub fn sd_box(p: Point2<f32>, center: Point2<f32>, half_size: Vector2<f32>) -> SignedDistance {
+    let offset = p - center;
+    let d = Vector2::new(
+        offset.x.abs() - half_size.x.abs(),
+        offset.y.abs() - half_size.y.abs(),
+    );
+    let outside = Vector2::new(d.x.max(0.0), d.y.max(0.0)).norm();
+    let inside = d.x.max(d.y).min(0.0);
+
+    SignedDistance(outside + inside)
+}
+
+pub fn sd_segment(p: Point2<f32>, a: Point2<f32>, b: Point2<f32>) -> SignedDistance {
+    SignedDistance(segment_distance(p, a, b))
+}
+
+fn segment_distance(p: Point2<f32>, a: Point2<f32>, b: Point2<f32>) -> f32 {
+    let pa = p - a;
+    let ba = b - a;
+    let length_squared = ba.norm_squared();
+
+    let h = if length_squared > 0.0 {
+        (pa.dot(&ba) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    (pa - ba * h).norm()
+}
+
+/// Distance to a closed polygon, using the crossing number to decide whether we're inside it
+pub fn sd_polygon(p: Point2<f32>, vertices: &[Point2<f32>]) -> SignedDistance {
+    if vertices.is_empty() {
+        return SignedDistance(f32::MAX);
+    }
+
+    let mut distance = f32::MAX;
+    let mut inside = false;
+
+    for (i, a) in vertices.iter().enumerate() {
+        let b = &vertices[(i + 1) % vertices.len()];
+
+        distance = distance.min(segment_distance(p, *a, *b));
+
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+    }
+
+    SignedDistance(if inside { -distance } else { distance })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_matches_box() {
+        let square = [
+            Point2::new(-0.5, -0.5),
+            Point2::new(0.5, -0.5),
+            Point2::new(0.5, 0.5),
+            Point2::new(-0.5, 0.5),
+        ];
+
+        for p in &[
+            Point2::new(0.0, 0.0),
+            Point2::new(0.25, -0.1),
+            Point2::new(0.9, 0.2),
+            Point2::new(-0.7, -0.8),
+        ] {
+            let polygon = sd_polygon(*p, &square).into_inner();
+            let square = sd_box(*p, Point2::origin(), Vector2::new(0.5, 0.5)).into_inner();
+
+            assert!((polygon - square).abs() < 0.0001);
+        }
+    }
+}
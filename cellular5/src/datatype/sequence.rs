@@ -0,0 +1,156 @@
+use lazy_static::lazy_static;
+
+const PRIME_SIEVE_LIMIT: usize = 1 << 16;
+//Collatz trajectories for u32 are all well under this, it's only here to bound the loop
+const MAX_COLLATZ_STEPS: u32 = 1024;
+
+lazy_static! {
+    static ref PRIMES: Vec<u32> = prime_sieve(PRIME_SIEVE_LIMIT);
+}
+
+fn prime_sieve(limit: usize) -> Vec<u32> {
+    let mut is_prime = vec![true; limit];
+    is_prime[0] = false;
+    is_prime[1] = false;
+
+    for i in 2..limit {
+        if is_prime[i] {
+            for multiple in (i * i..limit).step_by(i) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+
+    is_prime
+        .iter()
+        .enumerate()
+        .filter(|(_, prime)| **prime)
+        .map(|(i, _)| i as u32)
+        .collect()
+}
+
+/// The index-th fibonacci number mod modulus, using fast doubling so large indices are cheap
+pub fn fibonacci_mod(index: u64, modulus: u64) -> u64 {
+    if modulus <= 1 {
+        return 0;
+    }
+
+    //Widened so the squares can't overflow for 32 bit moduli
+    let modulus = u128::from(modulus);
+
+    // (F(k), F(k + 1)) for the bits of index read so far
+    let (mut a, mut b) = (0_u128, 1_u128);
+
+    for bit in (0..64 - index.leading_zeros()).rev() {
+        let doubled_a = a * ((2 * b + modulus - a) % modulus) % modulus;
+        let doubled_b = (a * a + b * b) % modulus;
+
+        if (index >> bit) & 1 == 0 {
+            a = doubled_a;
+            b = doubled_b;
+        } else {
+            a = doubled_b;
+            b = (doubled_a + doubled_b) % modulus;
+        }
+    }
+
+    a as u64
+}
+
+/// 2 to the power of index, mod modulus
+pub fn power_of_two_mod(index: u64, modulus: u64) -> u64 {
+    if modulus <= 1 {
+        return 0;
+    }
+
+    let mut result = 1;
+    let mut base = 2 % modulus;
+    let mut exponent = index;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// The index-th prime, wrapping back to 2 past the end of the sieve
+pub fn nth_prime(index: u32) -> u32 {
+    PRIMES[index as usize % PRIMES.len()]
+}
+
+/// Cycles through the 54 primes that fit in a byte, so a truncated large prime never shows up
+pub fn nth_byte_prime(index: u32) -> u8 {
+    let byte_primes = PRIMES.iter().take_while(|p| **p <= u32::from(u8::MAX)).count();
+
+    PRIMES[index as usize % byte_primes] as u8
+}
+
+/// The number of collatz steps it takes for index + 1 to reach 1
+pub fn collatz_stopping_time(index: u32) -> u32 {
+    let mut n = u64::from(index) + 1;
+    let mut steps = 0;
+
+    while n != 1 && steps < MAX_COLLATZ_STEPS {
+        n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+
+    steps
+}
+
+/// Counts up from 0 to range then back down again, repeating forever
+pub fn ping_pong(index: u64, range: u64) -> u64 {
+    if range == 0 {
+        return 0;
+    }
+
+    let phase = index % (2 * range);
+
+    if phase > range {
+        2 * range - phase
+    } else {
+        phase
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_match_naive() {
+        let mut fibonacci = (0_u64, 1_u64);
+
+        for i in 0..64 {
+            assert_eq!(fibonacci_mod(i, 1000), fibonacci.0 % 1000);
+            assert_eq!(power_of_two_mod(i, 1000), (1_u128 << i) as u64 % 1000);
+
+            fibonacci = (fibonacci.1, fibonacci.0 + fibonacci.1);
+        }
+
+        assert_eq!(&PRIMES[..6], &[2, 3, 5, 7, 11, 13]);
+        assert_eq!(collatz_stopping_time(0), 0);
+        assert_eq!(collatz_stopping_time(26), 111);
+        assert_eq!(
+            (0..8).map(|i| ping_pong(i, 3)).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 2, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn byte_primes_are_prime() {
+        let is_prime = |n: u32| n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0);
+
+        for i in 0..1000 {
+            assert!(is_prime(u32::from(nth_byte_prime(i))), "{}", nth_byte_prime(i));
+        }
+
+        assert_eq!(nth_byte_prime(53), 251);
+        assert_eq!(nth_byte_prime(54), 2);
+    }
+}
@@ -0,0 +1,108 @@
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+//Neighbour counts are scaled onto a Moore neighbourhood, so rules mean the same thing whatever
+//the size of the neighbourhood they're run over
+const BUCKETS: usize = 8;
+
+//(birth, survival) bitmasks, where bit n is set if n live neighbours give a live cell
+const PRESETS: &[(u16, u16)] = &[
+    // Life, B3/S23
+    (0b0_0000_1000, 0b0_0000_1100),
+    // HighLife, B36/S23
+    (0b0_0100_1000, 0b0_0000_1100),
+    // Seeds, B2/S
+    (0b0_0000_0100, 0b0_0000_0000),
+    // Day & Night, B3678/S34678
+    (0b1_1100_1000, 0b1_1101_1000),
+    // Maze, B3/S12345
+    (0b0_0000_1000, 0b0_0011_1110),
+];
+
+/// A birth/survival rule, where a cell's next state depends only on its own state and how many
+/// of its neighbours are alive
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OuterTotalisticRule {
+    birth: u16,
+    survival: u16,
+}
+
+impl OuterTotalisticRule {
+    pub fn life() -> Self {
+        let (birth, survival) = PRESETS[0];
+
+        Self { birth, survival }
+    }
+
+    pub fn next_state(self, alive: bool, live_neighbours: usize, neighbours: usize) -> bool {
+        let bucket = if neighbours == 0 {
+            0
+        } else {
+            ((live_neighbours * BUCKETS) as f32 / neighbours as f32).round() as usize
+        };
+
+        let mask = if alive { self.survival } else { self.birth };
+
+        mask & (1 << bucket) != 0
+    }
+}
+
+impl<'a> Generatable<'a> for OuterTotalisticRule {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        if rng.gen_bool(0.5) {
+            let (birth, survival) = *PRESETS.choose(rng).unwrap();
+
+            Self { birth, survival }
+        } else {
+            Self {
+                //Birth with no live neighbours makes the whole screen strobe, so it's left out
+                birth: rng.gen_range(0..1 << (BUCKETS + 1)) & !1,
+                survival: rng.gen_range(0..1 << (BUCKETS + 1)),
+            }
+        }
+    }
+}
+
+impl<'a> Mutatable<'a> for OuterTotalisticRule {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        match rng.gen_range(0..3) {
+            0 => *self = Self::generate_rng(rng, arg.into()),
+            1 => self.birth ^= 1 << rng.gen_range(1..=BUCKETS),
+            _ => self.survival ^= 1 << rng.gen_range(0..=BUCKETS),
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for OuterTotalisticRule {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a> UpdatableRecursively<'a> for OuterTotalisticRule {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn life_rule() {
+        let life = OuterTotalisticRule::life();
+
+        assert!(life.next_state(false, 3, 8));
+        assert!(!life.next_state(false, 2, 8));
+        assert!(life.next_state(true, 2, 8));
+        assert!(!life.next_state(true, 4, 8));
+        //Half of a 4 cell neighbourhood scales to 4 of 8
+        assert!(!life.next_state(true, 2, 4));
+    }
+}
@@ -0,0 +1,278 @@
+use log::warn;
+use mutagen::{Generatable, Mutatable, Updatable, UpdatableRecursively};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+use na::Point2;
+
+const MAX_STATES: usize = 4;
+const MAX_COLORS: usize = 4;
+
+/// Quarter turns clockwise, so a turn can be added straight onto a direction
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    None,
+    Right,
+    Back,
+    Left,
+}
+
+impl Turn {
+    const ALL: [Turn; 4] = [Turn::None, Turn::Right, Turn::Back, Turn::Left];
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TurmiteTransition {
+    pub write: usize,
+    pub turn: Turn,
+    pub next_state: usize,
+}
+
+impl TurmiteTransition {
+    fn random<R: Rng + ?Sized>(rng: &mut R, state_count: usize, color_count: usize) -> Self {
+        Self {
+            write: rng.gen_range(0..color_count),
+            turn: *Turn::ALL.choose(rng).unwrap(),
+            next_state: rng.gen_range(0..state_count),
+        }
+    }
+}
+
+/// A turmite's rule table, giving a transition for each pair of internal state and cell colour
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "TurmiteRuleData")]
+pub struct TurmiteRule {
+    state_count: usize,
+    color_count: usize,
+    transitions: Vec<TurmiteTransition>,
+}
+
+#[derive(Deserialize)]
+struct TurmiteRuleData {
+    state_count: usize,
+    color_count: usize,
+    transitions: Vec<TurmiteTransition>,
+}
+
+impl From<TurmiteRuleData> for TurmiteRule {
+    /// A hand edited or truncated table would panic when indexed, so it's swapped for a default
+    fn from(data: TurmiteRuleData) -> Self {
+        let valid = data.state_count > 0
+            && data.color_count >= 2
+            && data.transitions.len() == data.state_count * data.color_count
+            && data.transitions.iter().all(|t| t.write < data.color_count);
+
+        if valid {
+            Self {
+                state_count: data.state_count,
+                color_count: data.color_count,
+                transitions: data.transitions,
+            }
+        } else {
+            warn!("Invalid turmite rule table, falling back to Langton's ant");
+            Self::langtons_ant()
+        }
+    }
+}
+
+impl TurmiteRule {
+    pub fn langtons_ant() -> Self {
+        Self {
+            state_count: 1,
+            color_count: 2,
+            transitions: vec![
+                TurmiteTransition {
+                    write: 1,
+                    turn: Turn::Right,
+                    next_state: 0,
+                },
+                TurmiteTransition {
+                    write: 0,
+                    turn: Turn::Left,
+                    next_state: 0,
+                },
+            ],
+        }
+    }
+
+    pub fn color_count(&self) -> usize {
+        self.color_count
+    }
+
+    pub fn transition(&self, state: usize, color: usize) -> TurmiteTransition {
+        self.transitions
+            [(state % self.state_count) * self.color_count + color % self.color_count]
+    }
+}
+
+impl<'a> Generatable<'a> for TurmiteRule {
+    type GenArg = GenArg<'a>;
+
+    fn generate_rng<R: Rng + ?Sized>(rng: &mut R, _arg: GenArg<'a>) -> Self {
+        //Langton's ant is common enough to be worth seeing often
+        if rng.gen_bool(0.25) {
+            return Self::langtons_ant();
+        }
+
+        let state_count = rng.gen_range(1..=MAX_STATES);
+        let color_count = rng.gen_range(2..=MAX_COLORS);
+
+        Self {
+            state_count,
+            color_count,
+            transitions: (0..state_count * color_count)
+                .map(|_| TurmiteTransition::random(rng, state_count, color_count))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> Mutatable<'a> for TurmiteRule {
+    type MutArg = MutArg<'a>;
+
+    fn mutate_rng<R: Rng + ?Sized>(&mut self, rng: &mut R, arg: MutArg<'a>) {
+        if rng.gen_bool(0.5) {
+            *self = Self::generate_rng(rng, arg.into());
+        } else {
+            let (state_count, color_count) = (self.state_count, self.color_count);
+            *self.transitions.choose_mut(rng).unwrap() =
+                TurmiteTransition::random(rng, state_count, color_count);
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for TurmiteRule {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
+
+impl<'a> UpdatableRecursively<'a> for TurmiteRule {
+    fn update_recursively(&mut self, _arg: UpdArg<'a>) {}
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TurmiteAgent {
+    x: usize,
+    y: usize,
+    //0 is up, counting clockwise in quarter turns
+    direction: usize,
+    state: usize,
+}
+
+/// A set of agents walking the cell grid, drawing into their own buffer
+#[derive(Debug, Default)]
+pub struct TurmiteColony {
+    agents: Vec<TurmiteAgent>,
+    buffer: Buffer<FloatColor>,
+    //The rule colour last written to each cell, so agents taking several steps in an update see
+    //each other's writes rather than the same history step
+    written: Vec<Option<usize>>,
+}
+
+impl TurmiteColony {
+    pub fn get(&self, point: SNPoint) -> FloatColor {
+        self.buffer[point]
+    }
+
+    /// Moves each agent steps times. Agents read the colour under them from what the colony last
+    /// wrote there, or from the last history step for cells not yet visited. get_color is given
+    /// the cell being written and how far through the rule's colours the written colour is.
+    pub fn update<F: FnMut(SNPoint, UNFloat) -> FloatColor>(
+        &mut self,
+        rule: &TurmiteRule,
+        agent_count: usize,
+        steps: usize,
+        history: &History,
+        current_t: usize,
+        mut get_color: F,
+    ) {
+        let width = history.width();
+        let height = history.height();
+        let mut rng = thread_rng();
+
+        self.agents.truncate(agent_count);
+        while self.agents.len() < agent_count {
+            self.agents.push(TurmiteAgent {
+                x: rng.gen_range(0..width),
+                y: rng.gen_range(0..height),
+                direction: rng.gen_range(0..4),
+                state: 0,
+            });
+        }
+
+        //The history may have been resized since the last update
+        if self.written.len() != width * height {
+            self.written = vec![None; width * height];
+        }
+
+        let color_count = rule.color_count();
+
+        for _ in 0..steps {
+            for agent in self.agents.iter_mut() {
+                agent.x %= width;
+                agent.y %= height;
+
+                let color = self.written[agent.y * width + agent.x]
+                    .map(|color| color % color_count)
+                    .unwrap_or_else(|| {
+                        let brightness = FloatColor::from(history.get(
+                            agent.x,
+                            agent.y,
+                            current_t.saturating_sub(1),
+                        ))
+                        .get_average();
+
+                        ((brightness * color_count as f32) as usize).min(color_count - 1)
+                    });
+
+                let transition = rule.transition(agent.state, color);
+
+                let point = SNPoint::from_usize_range(
+                    Point2::new(agent.x, agent.y),
+                    Point2::new(0, 0),
+                    Point2::new(width, height),
+                );
+                self.buffer[point] = get_color(
+                    point,
+                    UNFloat::new(transition.write as f32 / (color_count - 1) as f32),
+                );
+                self.written[agent.y * width + agent.x] = Some(transition.write);
+
+                agent.direction = (agent.direction + transition.turn as usize) % 4;
+                agent.state = transition.next_state;
+
+                match agent.direction {
+                    0 => agent.y = (agent.y + height - 1) % height,
+                    1 => agent.x = (agent.x + 1) % width,
+                    2 => agent.y = (agent.y + 1) % height,
+                    _ => agent.x = (agent.x + width - 1) % width,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn langtons_ant_flips_colors() {
+        let rule = TurmiteRule::langtons_ant();
+
+        assert_eq!(rule.transition(0, 0).write, 1);
+        assert_eq!(rule.transition(0, 1).write, 0);
+        assert_eq!(rule.transition(3, 5), rule.transition(0, 1));
+    }
+
+    #[test]
+    fn invalid_rule_falls_back() {
+        let rule: TurmiteRule =
+            serde_yaml::from_str("state_count: 2\ncolor_count: 2\ntransitions: []").unwrap();
+
+        assert_eq!(rule, TurmiteRule::langtons_ant());
+    }
+}
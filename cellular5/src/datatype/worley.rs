@@ -0,0 +1,81 @@
+use crate::prelude::*;
+
+/// The distances to the closest and second closest points in a set, as used for Worley noise
+#[derive(Debug, Clone, Copy)]
+pub struct WorleyResult {
+    pub f1: f32,
+    pub f2: f32,
+    pub closest_index: usize,
+    pub point_count: usize,
+}
+
+impl WorleyResult {
+    pub fn new(
+        point: SNPoint,
+        points: &[SNPoint],
+        distance_function: &DistanceFunction,
+    ) -> Option<Self> {
+        let mut f1 = f32::MAX;
+        let mut f2 = f32::MAX;
+        let mut closest_index = 0;
+
+        for (i, other) in points.iter().enumerate() {
+            let distance =
+                distance_function.calculate_point2(point.into_inner(), other.into_inner());
+
+            if distance < f1 {
+                f2 = f1;
+                f1 = distance;
+                closest_index = i;
+            } else if distance < f2 {
+                f2 = distance;
+            }
+        }
+
+        if points.is_empty() {
+            None
+        } else {
+            Some(Self {
+                f1,
+                //With only one point there are no cell edges to find
+                f2: if points.len() > 1 { f2 } else { f1 },
+                closest_index,
+                point_count: points.len(),
+            })
+        }
+    }
+
+    /// Distance to the closest cell edge, zero along the edge itself
+    pub fn edge_distance(&self) -> f32 {
+        self.f2 - self.f1
+    }
+
+    pub fn cell_index(&self) -> UNFloat {
+        UNFloat::new(self.closest_index as f32 / self.point_count as f32)
+    }
+
+    /// A colour for the closest cell that stays the same as long as the point set keeps its order
+    pub fn cell_color(&self, seed: Byte) -> ByteColor {
+        let hash = hash_cell(self.closest_index as u32, u32::from(seed.into_inner()));
+
+        ByteColor {
+            r: Byte::new(hash as u8),
+            g: Byte::new((hash >> 8) as u8),
+            b: Byte::new((hash >> 16) as u8),
+            a: Byte::new(255),
+        }
+    }
+}
+
+/// Integer hash with decent avalanche, so neighbouring indices get unrelated colours
+fn hash_cell(index: u32, seed: u32) -> u32 {
+    let mut hash = index.wrapping_mul(0x9E37_79B9) ^ seed.wrapping_mul(0x85EB_CA6B);
+
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7FEB_352D);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846C_A68B);
+    hash ^= hash >> 16;
+
+    hash
+}
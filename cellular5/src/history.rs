@@ -1,5 +1,5 @@
 use ggez::{graphics::Image as GgImage, Context};
-use ndarray::{s, Array3, ArrayView1};
+use ndarray::{s, Array3, ArrayView1, ArrayView3};
 use rand::prelude::*;
 
 use crate::prelude::*;
@@ -11,6 +11,7 @@ pub struct HistoryStep {
 
     pub update_coordinate: CoordinateSet,
     pub frame_renderer: FrameRenderers,
+    pub blend: FrameBlend,
     pub root_scalar: UNFloat,
     pub fade_color: FloatColor,
     pub alpha_multiplier: UNFloat,
@@ -38,11 +39,18 @@ impl HistoryStep {
                 t: 0.0,
             },
             frame_renderer: FrameRenderers::default(),
+            blend: FrameBlend::default(),
             root_scalar: UNFloat::ZERO,
             fade_color: FloatColor::ALL_ZERO,
             alpha_multiplier: UNFloat::ZERO,
         }
     }
+
+    /// Resamples the cell array to a new size, so changing resolution doesn't cause a visible pop
+    pub fn resize(&mut self, ctx: &mut Context, array_width: usize, array_height: usize) {
+        self.cell_array = resample_cell_array(self.cell_array.view(), array_width, array_height);
+        self.computed_texture = compute_texture(ctx, self.cell_array.view(), false);
+    }
 }
 
 #[derive(Debug)]
@@ -59,6 +67,20 @@ impl History {
         }
     }
 
+    pub fn width(&self) -> usize {
+        self.history_steps[0].cell_array.dim().1
+    }
+
+    pub fn height(&self) -> usize {
+        self.history_steps[0].cell_array.dim().0
+    }
+
+    pub fn resize(&mut self, ctx: &mut Context, array_width: usize, array_height: usize) {
+        for step in self.history_steps.iter_mut() {
+            step.resize(ctx, array_width, array_height);
+        }
+    }
+
     pub fn get_raw(&self, x: usize, y: usize, t: usize) -> ArrayView1<u8> {
         let array = &self.history_steps[t % self.history_steps.len()].cell_array;
         array.slice(s![y % array.dim().0, x % array.dim().1, ..])
@@ -66,8 +88,8 @@ impl History {
 
     pub fn get_normalised(&self, pos: SNPoint, t: usize) -> FloatColor {
         self.get(
-            (pos.x().to_unsigned().into_inner() * CONSTS.cell_array_width as f32).round() as usize,
-            (pos.y().to_unsigned().into_inner() * CONSTS.cell_array_height as f32).round() as usize,
+            (pos.x().to_unsigned().into_inner() * self.width() as f32).round() as usize,
+            (pos.y().to_unsigned().into_inner() * self.height() as f32).round() as usize,
             t as usize,
         )
         .into()
@@ -94,3 +116,31 @@ fn init_cell_array(width: usize, height: usize) -> Array3<u8> {
         }
     })
 }
+
+/// Bilinearly resamples a cell array to the given dimensions
+pub fn resample_cell_array(cell_array: ArrayView3<u8>, width: usize, height: usize) -> Array3<u8> {
+    let (old_height, old_width, _) = cell_array.dim();
+
+    let x_ratio = old_width as f32 / width as f32;
+    let y_ratio = old_height as f32 / height as f32;
+
+    Array3::from_shape_fn((height, width, 4), |(y, x, c)| {
+        let src_x = ((x as f32 + 0.5) * x_ratio - 0.5).max(0.0);
+        let src_y = ((y as f32 + 0.5) * y_ratio - 0.5).max(0.0);
+
+        let x0 = (src_x as usize).min(old_width - 1);
+        let y0 = (src_y as usize).min(old_height - 1);
+        let x1 = (x0 + 1).min(old_width - 1);
+        let y1 = (y0 + 1).min(old_height - 1);
+
+        let fx = src_x - x0 as f32;
+        let fy = src_y - y0 as f32;
+
+        let top = f32::from(cell_array[[y0, x0, c]]) * (1.0 - fx)
+            + f32::from(cell_array[[y0, x1, c]]) * fx;
+        let bottom = f32::from(cell_array[[y1, x0, c]]) * (1.0 - fx)
+            + f32::from(cell_array[[y1, x1, c]]) * fx;
+
+        (top * (1.0 - fy) + bottom * fy).round() as u8
+    })
+}
@@ -0,0 +1,312 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local};
+use failure::{format_err, Fallible};
+use ggez::{
+    graphics::{self, Color as GgColor, DrawParam, Image as GgImage, Text},
+    Context, GameResult,
+};
+use image::RgbaImage;
+use log::{info, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{prelude::*, update_stat::UpdateStat};
+
+pub const THUMBNAIL_SIZE: u32 = 64;
+
+const TREE_FILENAME: &str = "tree.yml";
+const THUMBNAIL_FILENAME: &str = "thumbnail.png";
+const METADATA_FILENAME: &str = "metadata.yml";
+
+const GRID_COLUMNS: usize = 4;
+
+pub fn library_path() -> PathBuf {
+    local_path("library")
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryMetadata {
+    //Stored as a string since u128 doesn't survive every serde format
+    pub seed: String,
+    pub date: DateTime<Local>,
+    pub update_stat: UpdateStat,
+}
+
+/// Saves a new library entry, returning the directory it was saved to
+pub fn save_entry<T: Serialize>(
+    tree: &T,
+    thumbnail: &RgbaImage,
+    metadata: &LibraryMetadata,
+) -> Fallible<PathBuf> {
+    save_entry_to(&library_path(), tree, thumbnail, metadata)
+}
+
+fn save_entry_to<T: Serialize>(
+    library: &Path,
+    tree: &T,
+    thumbnail: &RgbaImage,
+    metadata: &LibraryMetadata,
+) -> Fallible<PathBuf> {
+    let path = library.join(metadata.date.format("%Y-%m-%d_%H-%M-%S%.3f").to_string());
+
+    fs::create_dir_all(&path)?;
+    fs::write(path.join(TREE_FILENAME), serde_yaml::to_vec(tree)?)?;
+    fs::write(path.join(METADATA_FILENAME), serde_yaml::to_vec(metadata)?)?;
+    thumbnail.save(path.join(THUMBNAIL_FILENAME))?;
+
+    info!("Saved tree to library entry {}", path.to_string_lossy());
+
+    Ok(path)
+}
+
+pub fn load_tree<T: DeserializeOwned>(entry_path: &Path) -> Fallible<T> {
+    info!("Loading tree from library entry {}", entry_path.to_string_lossy());
+
+    Ok(serde_yaml::from_slice(&fs::read(entry_path.join(TREE_FILENAME))?)?)
+}
+
+pub struct LibraryEntry {
+    pub path: PathBuf,
+    pub metadata: LibraryMetadata,
+    pub thumbnail: Option<GgImage>,
+}
+
+impl LibraryEntry {
+    fn load(ctx: &mut Context, path: PathBuf) -> Fallible<Self> {
+        let metadata = serde_yaml::from_slice(&fs::read(path.join(METADATA_FILENAME))?)?;
+
+        let thumbnail = match image::open(path.join(THUMBNAIL_FILENAME)) {
+            Ok(image) => {
+                let image = image.to_rgba8();
+                let (width, height) = image.dimensions();
+
+                let mut thumbnail =
+                    GgImage::from_rgba8(ctx, width as u16, height as u16, &image.into_raw())
+                        .map_err(|e| format_err!("{}", e))?;
+                thumbnail.set_filter(graphics::FilterMode::Nearest);
+
+                Some(thumbnail)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to load thumbnail for library entry {}: {}",
+                    path.to_string_lossy(),
+                    e
+                );
+                None
+            }
+        };
+
+        Ok(Self {
+            path,
+            metadata,
+            thumbnail,
+        })
+    }
+}
+
+/// An in-window overlay showing a grid of library entries
+#[derive(Default)]
+pub struct LibraryBrowser {
+    entries: Vec<LibraryEntry>,
+    selected: usize,
+    open: bool,
+}
+
+impl LibraryBrowser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self, ctx: &mut Context) {
+        self.refresh(ctx)
+            .unwrap_or_else(|e| warn!("Failed to read library: {}", e));
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        // Thumbnails are reloaded on open, no need to keep the textures around
+        self.entries.clear();
+    }
+
+    pub fn refresh(&mut self, ctx: &mut Context) -> Fallible<()> {
+        self.entries.clear();
+
+        let path = library_path();
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let mut entry_paths: Vec<_> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.join(TREE_FILENAME).exists())
+            .collect();
+
+        // Entries are named by date, so this puts the newest first
+        entry_paths.sort();
+        entry_paths.reverse();
+
+        for entry_path in entry_paths {
+            match LibraryEntry::load(ctx, entry_path.clone()) {
+                Ok(entry) => self.entries.push(entry),
+                Err(e) => warn!(
+                    "Failed to load library entry {}: {}",
+                    entry_path.to_string_lossy(),
+                    e
+                ),
+            }
+        }
+
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+
+        Ok(())
+    }
+
+    pub fn move_selection(&mut self, dx: isize, dy: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let selected = self.selected as isize + dx + dy * GRID_COLUMNS as isize;
+        self.selected = selected.max(0).min(self.entries.len() as isize - 1) as usize;
+    }
+
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.entries
+            .get(self.selected)
+            .map(|entry| entry.path.as_path())
+    }
+
+    pub fn draw(&self, ctx: &mut Context, blank_texture: &GgImage) -> GameResult<()> {
+        let window_width = CONSTS.initial_window_width;
+        let window_height = CONSTS.initial_window_height;
+
+        graphics::draw(
+            ctx,
+            blank_texture,
+            DrawParam::new()
+                .color(GgColor::new(0.0, 0.0, 0.0, 0.75))
+                .scale([window_width, window_height]),
+        )?;
+
+        if self.entries.is_empty() {
+            graphics::draw(
+                ctx,
+                &Text::new(format!(
+                    "Library is empty, save entries to {}",
+                    library_path().to_string_lossy()
+                )),
+                DrawParam::new().dest([8.0, 8.0]),
+            )?;
+
+            return Ok(());
+        }
+
+        let cell_size = window_width / GRID_COLUMNS as f32;
+        let padding = cell_size * 0.05;
+        let visible_rows = ((window_height / cell_size) as usize).max(1);
+
+        let selected_row = self.selected / GRID_COLUMNS;
+        let first_row = (selected_row + 1).saturating_sub(visible_rows);
+
+        for (i, entry) in self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(first_row * GRID_COLUMNS)
+            .take(visible_rows * GRID_COLUMNS)
+        {
+            let x = (i % GRID_COLUMNS) as f32 * cell_size;
+            let y = (i / GRID_COLUMNS - first_row) as f32 * cell_size;
+
+            if i == self.selected {
+                graphics::draw(
+                    ctx,
+                    blank_texture,
+                    DrawParam::new()
+                        .dest([x, y])
+                        .scale([cell_size, cell_size]),
+                )?;
+            }
+
+            if let Some(thumbnail) = &entry.thumbnail {
+                let size = cell_size - padding * 2.0;
+
+                graphics::draw(
+                    ctx,
+                    thumbnail,
+                    DrawParam::new().dest([x + padding, y + padding]).scale([
+                        size / thumbnail.width() as f32,
+                        size / thumbnail.height() as f32,
+                    ]),
+                )?;
+            }
+        }
+
+        let selected = &self.entries[self.selected];
+        let stat = &selected.metadata.update_stat;
+
+        graphics::draw(
+            ctx,
+            &Text::new(format!(
+                "{} | seed {} | activity {:.3} alpha {:.3} local {:.3} global {:.3}",
+                selected.metadata.date.format("%Y-%m-%d %H:%M:%S"),
+                selected.metadata.seed,
+                stat.activity_value,
+                stat.alpha_value,
+                stat.local_similarity_value,
+                stat.global_similarity_value,
+            )),
+            DrawParam::new()
+                .dest([8.0, window_height - 24.0])
+                .color(GgColor::new(1.0, 1.0, 1.0, 1.0)),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let library =
+            std::env::temp_dir().join(format!("cellular5_library_{}", std::process::id()));
+
+        let tree = vec![(1u32, "a".to_string()), (2, "b".to_string())];
+        let metadata = LibraryMetadata {
+            seed: "42".to_string(),
+            date: chrono::Local::now(),
+            update_stat: UpdateStat::default(),
+        };
+        let thumbnail = RgbaImage::new(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+
+        let entry = save_entry_to(&library, &tree, &thumbnail, &metadata).unwrap();
+        let loaded: Vec<(u32, String)> = load_tree(&entry).unwrap();
+
+        let loaded_metadata: LibraryMetadata =
+            serde_yaml::from_slice(&fs::read(entry.join(METADATA_FILENAME)).unwrap()).unwrap();
+        let loaded_thumbnail = image::open(entry.join(THUMBNAIL_FILENAME)).unwrap();
+
+        fs::remove_dir_all(&library).unwrap();
+
+        assert_eq!(loaded, tree);
+        assert_eq!(loaded_metadata.seed, metadata.seed);
+        assert_eq!(loaded_metadata.update_stat, metadata.update_stat);
+        assert_eq!(
+            loaded_thumbnail.to_rgba8().dimensions(),
+            (THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        );
+    }
+}
@@ -6,18 +6,21 @@ extern crate gfx;
 
 use std::{
     fs,
+    path::PathBuf,
     rc::Rc,
     thread,
     time::{Duration, Instant},
 };
 
 use cpu_monitor::CpuInstant;
+use failure::Fallible;
+use image::RgbaImage;
 use ggez::{
     conf::{FullscreenType, WindowMode, WindowSetup},
-    event::{self, EventHandler, KeyCode, KeyMods},
+    event::{self, EventHandler, KeyCode, KeyMods, MouseButton},
     graphics,
     graphics::{Image as GgImage, DrawParam},
-    input::keyboard,
+    input::{keyboard, mouse},
     mint::Point2,
     timer, Context, ContextBuilder, GameResult,
 };
@@ -26,11 +29,13 @@ use mutagen::{Generatable, Mutatable, Reborrow, Updatable, UpdatableRecursively}
 use ndarray::{s, ArrayViewMut1, Axis as NdAxis};
 use rand::prelude::*;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
 
 use crate::{
-    arena_wrappers::*, data_set::*, history::*, node_set::*, opts::Opts, prelude::*, ui::*,
-    update_stat::UpdateStat,
+    arena_wrappers::*, data_set::*, history::*, library::*, node_set::*, opts::Opts,
+    output_palette::OutputPalette, post_process::{new_shadertoy, PostProcess}, prelude::*,
+    resolution::Resolution, ui::*, undo_stack::*, update_stat::UpdateStat, video_wall::VideoWall,
 };
 
 use protoplasm::util::*;
@@ -68,18 +73,28 @@ pub mod coordinate_set;
 pub mod data_set;
 pub mod datatype;
 pub mod gamepad;
-pub mod gfx_renderer;
 pub mod history;
+pub mod library;
 pub mod mic;
 pub mod mutagen_args;
 pub mod node;
 pub mod node_set;
 pub mod opts;
+pub mod output_palette;
+pub mod post_process;
 pub mod preloader;
 pub mod prelude;
+pub mod resolution;
 pub mod ui;
+pub mod undo_stack;
 pub mod update_stat;
 pub mod util;
+pub mod video_wall;
+
+const SCREENSAVER_GRACE_PERIOD: Duration = Duration::from_secs(1);
+/// How far the mouse has to move in one event, in pixels, to end the screensaver
+const SCREENSAVER_MOTION_THRESHOLD: f32 = 8.0;
+const SCREENSAVER_AXIS_THRESHOLD: f32 = 0.5;
 
 fn main() {
     std::env::set_var("RUST_BACKTRACE", "full");
@@ -90,16 +105,29 @@ fn main() {
     // The preloader can take a while to destroy since it may be waiting on IO/network,and we want the window to close responsively
     let image_preloader = Rc::new(Preloader::new(32, RandomImageLoader::new));
 
+    let video_wall_config = CONSTS
+        .video_wall
+        .as_ref()
+        .filter(|config| VideoWall::validate(config).is_ok());
+
+    let window_mode = if let Some(config) = video_wall_config {
+        let (width, height) = VideoWall::window_size(config);
+
+        WindowMode::default()
+            .dimensions(width, height)
+            .borderless(true)
+    } else {
+        WindowMode::default()
+            .dimensions(CONSTS.initial_window_width, CONSTS.initial_window_height)
+            .fullscreen_type(if CONSTS.fullscreen || CONSTS.screensaver {
+                FullscreenType::Desktop
+            } else {
+                FullscreenType::Windowed
+            })
+    };
+
     let (mut ctx, event_loop) = ContextBuilder::new("cellular4", "CodeBunny")
-        .window_mode(
-            WindowMode::default()
-                .dimensions(CONSTS.initial_window_width, CONSTS.initial_window_height)
-                .fullscreen_type(if CONSTS.fullscreen {
-                    FullscreenType::Desktop
-                } else {
-                    FullscreenType::Windowed
-                }),
-        )
+        .window_mode(window_mode)
         .window_setup(
             WindowSetup::default()
                 .title("Cellular 4")
@@ -137,13 +165,16 @@ fn setup_logging(ui: &Ui) {
         .unwrap();
 }
 
-#[derive(Debug, Generatable, Mutatable, UpdatableRecursively)]
+#[derive(Debug, Generatable, Mutatable, UpdatableRecursively, Serialize, Deserialize)]
 #[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
 struct NodeTree {
     /// The root node for the tree that computes the next screen state
     root_node: GenericColorNodes,
     root_coordinate_node: NodeBox<CoordMapNodes>,
     root_frame_renderer: NodeBox<FrameRendererNodes>,
+    //Not boxed so genomes saved before blend modes existed still load, drawing with Alpha
+    #[serde(default)]
+    root_blend_mode: BlendModeNodes,
     compute_offset_node: NodeBox<CoordMapNodes>,
     fade_color_node: GenericColorNodes,
     fade_color_alpha_multiplier: NodeBox<UNFloatNodes>,
@@ -242,15 +273,30 @@ struct MyGame {
     data: DataSet,
 
     node_tree: NodeTree,
+    //Snapshots of the node tree and the node sets it references, taken before each mutation
+    undo_stack: UndoStack,
+    undo_request: Option<UndoDirection>,
+
+    library_browser: LibraryBrowser,
+    library_load_request: Option<PathBuf>,
 
     //record_tree: bool,
     tree_dirty: bool,
+    resolution: Resolution,
+    output_palette: Option<OutputPalette>,
+    post_process: Option<PostProcess>,
+    video_wall: Option<VideoWall>,
+    //Which slice of the cell array we're computing this tic, reset every update
+    slice_index: usize,
     current_t: usize,
     time_elapsed: f32,
     last_mutation_t: usize,
     last_render_t: usize,
     cpu_t: CpuInstant,
     last_update_time: Instant,
+    /// Time spent sleeping between slices this update, which doesn't count towards its load
+    slice_sleep: Duration,
+    screensaver_exiting: bool,
     rng: DeterministicRng,
     ui: Ui,
 
@@ -275,10 +321,12 @@ impl MyGame {
 
         let mut rng = DeterministicRng::new();
 
+        let resolution = Resolution::new();
+
         let history = History::new(
             ctx,
-            CONSTS.cell_array_width,
-            CONSTS.cell_array_height,
+            resolution.width(),
+            resolution.height(),
             CONSTS.cell_array_history_length,
         );
 
@@ -325,6 +373,49 @@ impl MyGame {
             (None, None)
         };
 
+        let output_palette = CONSTS.output_palette.as_ref().and_then(|config| {
+            OutputPalette::load(config)
+                .map_err(|e| warn!("Failed to load output palette: {}", e))
+                .ok()
+        });
+
+        let mut post_process = CONSTS.post_process.as_ref().and_then(|config| {
+            PostProcess::load(ctx, config)
+                .map_err(|e| warn!("Failed to load post processing shaders: {}", e))
+                .ok()
+        });
+
+        //Frame renderers and shaders blend between cells, so the palette is applied again as the
+        //very last pass to keep what's on screen inside it
+        if let Some(output_palette) = &output_palette {
+            match (
+                new_shadertoy(ctx, &output_palette.shadertoy_source()),
+                post_process.as_mut(),
+            ) {
+                (Ok(pass), Some(post_process)) => post_process.push_pass(pass),
+                (Ok(pass), None) => {
+                    post_process = PostProcess::new(ctx, vec![pass])
+                        .map_err(|e| warn!("Failed to set up output palette shader: {}", e))
+                        .ok();
+                }
+                (Err(e), _) => warn!("Failed to build output palette shader: {}", e),
+            }
+        }
+
+        let video_wall = CONSTS.video_wall.as_ref().and_then(|config| {
+            VideoWall::new(ctx, config)
+                .map_err(|e| warn!("Failed to set up video wall: {}", e))
+                .ok()
+        });
+
+        if video_wall.is_some() && (CONSTS.fullscreen || CONSTS.screensaver) {
+            warn!("Ignoring fullscreen, the video wall sizes the window to fit its tiles instead");
+        }
+
+        if CONSTS.screensaver {
+            mouse::set_cursor_hidden(ctx, true);
+        }
+
         let mut gamepads = Gamepads::new();
         let mut mouse_position = ggez::input::mouse::position(ctx);
 
@@ -332,8 +423,8 @@ impl MyGame {
             blank_texture: compute_blank_texture(ctx),
             next_history_step: HistoryStep::new(
                 ctx,
-                CONSTS.cell_array_width,
-                CONSTS.cell_array_height,
+                resolution.width(),
+                resolution.height(),
                 false,
             ),
             rolling_update_stat_total: UpdateStat {
@@ -374,14 +465,27 @@ impl MyGame {
             nodes,
             data,
 
+            undo_stack: UndoStack::new(CONSTS.undo_stack_length),
+            undo_request: None,
+
+            library_browser: LibraryBrowser::new(),
+            library_load_request: None,
+
             //record_tree: false,
             tree_dirty: false,
+            resolution,
+            output_palette,
+            post_process,
+            video_wall,
+            slice_index: 0,
             current_t: 0,
             time_elapsed: 0.0,
             last_mutation_t: 0,
             last_render_t: 0,
             cpu_t: CpuInstant::now().unwrap(),
             last_update_time: Instant::now(),
+            slice_sleep: Duration::ZERO,
+            screensaver_exiting: false,
             ui,
             rng,
             history,
@@ -397,19 +501,115 @@ impl MyGame {
     }
 }
 
+impl MyGame {
+    /// Shrinks the most recently computed frame down to a thumbnail. Stateful and history based
+    /// nodes only look right after running for a while, so the live frame is used rather than
+    /// evaluating the tree from scratch.
+    fn render_thumbnail(&self) -> RgbaImage {
+        let history_len = self.history.history_steps.len();
+        let current_step =
+            &self.history.history_steps[(self.current_t + history_len - 1) % history_len];
+
+        let mut thumbnail = resample_cell_array(
+            current_step.cell_array.view(),
+            THUMBNAIL_SIZE as usize,
+            THUMBNAIL_SIZE as usize,
+        );
+        thumbnail.slice_mut(s![.., .., 3]).fill(255);
+
+        RgbaImage::from_raw(THUMBNAIL_SIZE, THUMBNAIL_SIZE, thumbnail.into_raw_vec())
+            .expect("Thumbnail size didn't match its dimensions")
+    }
+
+    fn save_to_library(&self) {
+        let metadata = LibraryMetadata {
+            seed: RNG_SEED.lock().unwrap().to_string(),
+            date: chrono::Local::now(),
+            update_stat: self.average_update_stat,
+        };
+
+        save_entry(
+            &(&self.node_tree, &self.nodes),
+            &self.render_thumbnail(),
+            &metadata,
+        )
+        .map(|_| ())
+        .unwrap_or_else(|e| warn!("Failed to save tree to library: {}", e));
+    }
+
+    /// Saves the tree and quits if we're running as a screensaver, returning whether we are
+    fn exit_screensaver(&mut self, ctx: &mut Context) -> bool {
+        if !CONSTS.screensaver {
+            return false;
+        }
+
+        //Some platforms send a burst of input as the fullscreen window opens
+        if timer::time_since_start(ctx) < SCREENSAVER_GRACE_PERIOD {
+            return true;
+        }
+
+        //Several events can arrive in the same frame, but the tree only needs saving once
+        if !self.screensaver_exiting {
+            self.screensaver_exiting = true;
+            self.save_to_library();
+            event::quit(ctx);
+        }
+
+        true
+    }
+}
+
 impl EventHandler<ggez::GameError> for MyGame {
+    fn resize_event(&mut self, ctx: &mut Context, _width: f32, _height: f32) {
+        if let Some(post_process) = &mut self.post_process {
+            post_process
+                .resize(ctx)
+                .unwrap_or_else(|e| warn!("Failed to resize post processing canvases: {}", e));
+        }
+    }
+
     fn key_down_event(
         &mut self,
         ctx: &mut Context,
         keycode: KeyCode,
-        _keymods: KeyMods,
+        keymods: KeyMods,
         _repeat: bool,
     ) {
+        if self.exit_screensaver(ctx) {
+            return;
+        }
+
+        if self.library_browser.is_open() {
+            match keycode {
+                KeyCode::Escape | KeyCode::L => self.library_browser.close(),
+                KeyCode::Left => self.library_browser.move_selection(-1, 0),
+                KeyCode::Right => self.library_browser.move_selection(1, 0),
+                KeyCode::Up => self.library_browser.move_selection(0, -1),
+                KeyCode::Down => self.library_browser.move_selection(0, 1),
+                KeyCode::Return => {
+                    self.library_load_request =
+                        self.library_browser.selected_path().map(PathBuf::from);
+                    self.library_browser.close();
+                }
+                _ => {}
+            }
+
+            return;
+        }
+
         if keycode == KeyCode::Escape {
             event::quit(ctx);
             return;
         }
 
+        match keycode {
+            KeyCode::Left => self.undo_request = Some(UndoDirection::Back),
+            KeyCode::Right => self.undo_request = Some(UndoDirection::Forward),
+            KeyCode::L => self.library_browser.open(ctx),
+            KeyCode::S if keymods.contains(KeyMods::CTRL) => self.save_to_library(),
+            _ => {}
+        }
+
         // if !repeat {
         //     let save_slot = match keycode {
         //         KeyCode::Key1 => Some("1"),
@@ -456,11 +656,40 @@ impl EventHandler<ggez::GameError> for MyGame {
         // }
     }
 
+    fn mouse_button_down_event(
+        &mut self,
+        ctx: &mut Context,
+        _button: MouseButton,
+        _x: f32,
+        _y: f32,
+    ) {
+        self.exit_screensaver(ctx);
+    }
+
+    fn mouse_motion_event(&mut self, ctx: &mut Context, _x: f32, _y: f32, dx: f32, dy: f32) {
+        //Small jitter from the mouse or the window opening shouldn't end the screensaver
+        if dx.abs() + dy.abs() > SCREENSAVER_MOTION_THRESHOLD {
+            self.exit_screensaver(ctx);
+        }
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, _y: f32) {
+        self.exit_screensaver(ctx);
+    }
+
     fn gamepad_button_down_event(&mut self, ctx: &mut Context, _btn: GgButton, id: GgGamepadId) {
+        if self.exit_screensaver(ctx) {
+            return;
+        }
+
         self.gamepads.register_gamepad(ctx, id);
     }
 
     fn gamepad_button_up_event(&mut self, ctx: &mut Context, _btn: GgButton, id: GgGamepadId) {
+        if self.exit_screensaver(ctx) {
+            return;
+        }
+
         self.gamepads.register_gamepad(ctx, id);
     }
 
@@ -468,13 +697,23 @@ impl EventHandler<ggez::GameError> for MyGame {
         &mut self,
         ctx: &mut Context,
         _axis: GgAxis,
-        _value: f32,
+        value: f32,
         id: GgGamepadId,
     ) {
+        if value.abs() > SCREENSAVER_AXIS_THRESHOLD && self.exit_screensaver(ctx) {
+            return;
+        }
+
         self.gamepads.register_gamepad(ctx, id);
     }
 
     fn quit_event(&mut self, _ctx: &mut Context) -> bool {
+        //Closing the window skips the input that normally ends the screensaver, so save here too
+        if CONSTS.screensaver && !self.screensaver_exiting {
+            self.screensaver_exiting = true;
+            self.save_to_library();
+        }
+
         if let Some(profiler) = &self.profiler {
             println!("Saving profiler graphs...");
 
@@ -495,9 +734,13 @@ impl EventHandler<ggez::GameError> for MyGame {
 
         let current_t = self.current_t;
 
-        let slice_height = CONSTS.cell_array_height / CONSTS.tics_per_update;
-        let slice_y = (timer::ticks(ctx) % CONSTS.tics_per_update) * slice_height;
-        let slice_y_range = slice_y..slice_y + slice_height;
+        let width = self.history.width();
+        let height = self.history.height();
+        let tics_per_update = self.resolution.tics_per_update();
+
+        let slice_height = (height + tics_per_update - 1) / tics_per_update;
+        let slice_y = (self.slice_index * slice_height).min(height);
+        let slice_y_range = slice_y..(slice_y + slice_height).min(height);
 
         let mut new_update_slice =
             self.next_history_step
@@ -518,15 +761,15 @@ impl EventHandler<ggez::GameError> for MyGame {
         let root_coordinate_node = &self.node_tree.root_coordinate_node;
         let nodes = &self.nodes;
         let data = &self.data;
-        let total_cells = CONSTS.cell_array_width * CONSTS.cell_array_height;
+        let total_cells = width * height;
 
         let t_coord = self.time_elapsed;
+        let output_palette = &self.output_palette;
 
         let update_step = |y, x, mut new: ArrayViewMut1<u8>| {
             let coordinate_set = CoordinateSet {
-                x: UNFloat::new(x as f32 / CONSTS.cell_array_width as f32).to_signed(),
-                y: UNFloat::new((y + slice_y as usize) as f32 / CONSTS.cell_array_height as f32)
-                    .to_signed(),
+                x: UNFloat::new(x as f32 / width as f32).to_signed(),
+                y: UNFloat::new((y + slice_y as usize) as f32 / height as f32).to_signed(),
                 // t: current_t as f32,
                 t: t_coord,
             };
@@ -546,10 +789,14 @@ impl EventHandler<ggez::GameError> for MyGame {
 
             let transformed_coords = root_coordinate_node.compute(compute_arg.reborrow());
 
-            let new_color = ByteColor::from(
+            let mut new_color = ByteColor::from(
                 root_node.compute(compute_arg.replace_coordinate_set(&transformed_coords)),
             );
 
+            if let Some(output_palette) = output_palette {
+                new_color = output_palette.quantise(new_color, x, y + slice_y);
+            }
+
             new[0] = new_color.r.into_inner();
             new[1] = new_color.g.into_inner();
             new[2] = new_color.b.into_inner();
@@ -565,13 +812,13 @@ impl EventHandler<ggez::GameError> for MyGame {
             let local_color = history.get(
                 (x as i32 + local_offset.0)
                     .max(0)
-                    .min(CONSTS.cell_array_width as i32 - 1) as usize,
-                (y as i32 + local_offset.1).min(CONSTS.cell_array_height as i32 - 1) as usize,
+                    .min(width as i32 - 1) as usize,
+                (y as i32 + local_offset.1).min(height as i32 - 1) as usize,
                 current_t,
             );
             let global_color = history.get(
-                random::<usize>() % CONSTS.cell_array_width,
-                random::<usize>() % CONSTS.cell_array_height,
+                random::<usize>() % width,
+                random::<usize>() % height,
                 current_t,
             );
 
@@ -609,7 +856,14 @@ impl EventHandler<ggez::GameError> for MyGame {
 
         self.rolling_update_stat_total += slice_update_stat;
 
-        if timer::ticks(ctx) % CONSTS.tics_per_update == 0 {
+        self.slice_index = (self.slice_index + 1) % tics_per_update;
+
+        if self.slice_index == 0 {
+            //Error diffusion has to visit cells in order, so it waits for the last slice
+            if let Some(output_palette) = &self.output_palette {
+                output_palette.diffuse_errors(self.next_history_step.cell_array.view_mut());
+            }
+
             self.time_elapsed = timer::time_since_start(ctx).as_secs_f32();
 
             self.gamepads.update(ctx);
@@ -663,7 +917,44 @@ impl EventHandler<ggez::GameError> for MyGame {
             let history_index = self.current_t.saturating_sub(1) % history_len;
             let history_step = &self.history.history_steps[history_index];
 
-            if self.tree_dirty
+            if let Some(path) = self.library_load_request.take() {
+                match load_tree::<(NodeTree, Vec<NodeSet>)>(&path) {
+                    Ok((node_tree, nodes)) => {
+                        self.undo_stack
+                            .push(&(&self.node_tree, &self.nodes))
+                            .unwrap_or_else(|e| warn!("Failed to snapshot tree: {}", e));
+
+                        self.node_tree = node_tree;
+                        self.nodes = nodes;
+                        self.last_mutation_t = self.current_t;
+                        self.tree_dirty = false;
+                    }
+                    Err(e) => warn!(
+                        "Failed to load library entry {}: {}",
+                        path.to_string_lossy(),
+                        e
+                    ),
+                }
+            } else if let Some(direction) = self.undo_request.take() {
+                let restored: Fallible<Option<(NodeTree, Vec<NodeSet>)>> = self
+                    .undo_stack
+                    .step(direction, &(&self.node_tree, &self.nodes));
+
+                match restored {
+                    Ok(Some((node_tree, nodes))) => {
+                        info!(
+                            "====TIC: {} STEPPING {:?} IN UNDO STACK====",
+                            self.current_t, direction
+                        );
+                        self.node_tree = node_tree;
+                        self.nodes = nodes;
+                        self.last_mutation_t = self.current_t;
+                        self.tree_dirty = false;
+                    }
+                    Ok(None) => info!("Nothing to step {:?} to in undo stack", direction),
+                    Err(e) => warn!("Failed to step {:?} in undo stack: {}", direction, e),
+                }
+            } else if self.tree_dirty
                 || (CONSTS.auto_mutate
                     && (
                         cpu_usage >= CONSTS.auto_mutate_above_cpu_usage
@@ -673,6 +964,10 @@ impl EventHandler<ggez::GameError> for MyGame {
             {
                 info!("====TIC: {} MUTATING TREE====", self.current_t);
 
+                self.undo_stack
+                    .push(&(&self.node_tree, &self.nodes))
+                    .unwrap_or_else(|e| warn!("Failed to snapshot tree: {}", e));
+
                 let mut_arg = 
                     MutArg {
                         nodes: &mut self.nodes,
@@ -709,12 +1004,18 @@ impl EventHandler<ggez::GameError> for MyGame {
                                 &mut self.rng,
                                 mut_arg,
                             );
-                        } else {
+                        } else if thread_rng().gen_bool(0.5) {
                             info!("MUTATING RENDERER");
                             self.node_tree.root_frame_renderer.mutate_rng(
                                 &mut self.rng,
                                 mut_arg,
                             );
+                        } else {
+                            info!("MUTATING BLEND MODE");
+                            self.node_tree.root_blend_mode.mutate_rng(
+                                &mut self.rng,
+                                mut_arg,
+                            );
                         }
                     }
                 }
@@ -796,6 +1097,11 @@ impl EventHandler<ggez::GameError> for MyGame {
                 .root_frame_renderer
                 .compute(step_com_arg.reborrow());
 
+            self.next_history_step.blend = self
+                .node_tree
+                .root_blend_mode
+                .compute(step_com_arg.reborrow());
+
             let use_nearest_neighbour_scaling = self
                 .node_tree
                 .scaling_mode_node
@@ -851,8 +1157,21 @@ impl EventHandler<ggez::GameError> for MyGame {
             let update_delta = Duration::from_secs_f64(1.0 / CONSTS.target_fps as f64);
             let mut next_update_time = self.last_update_time + update_delta;
 
-            // Drop updates if we're more than one update behind
             let now = Instant::now();
+
+            let busy_time = now
+                .saturating_duration_since(self.last_update_time)
+                .saturating_sub(self.slice_sleep);
+            self.slice_sleep = Duration::ZERO;
+
+            if self.resolution.record_update(busy_time) {
+                self.history
+                    .resize(ctx, self.resolution.width(), self.resolution.height());
+                self.next_history_step
+                    .resize(ctx, self.resolution.width(), self.resolution.height());
+            }
+
+            // Drop updates if we're more than one update behind
             while now > next_update_time + update_delta {
                 next_update_time += update_delta;
             }
@@ -864,6 +1183,21 @@ impl EventHandler<ggez::GameError> for MyGame {
             } else {
                 timer::yield_now();
             }
+        } else if CONSTS.screensaver {
+            //Spread slices over the update rather than running them back to back, so the CPU can
+            //idle between them
+            let update_delta = Duration::from_secs_f64(1.0 / CONSTS.target_fps as f64);
+            let slice_time = self.last_update_time
+                + update_delta.mul_f64(self.slice_index as f64 / tics_per_update as f64);
+
+            let now = Instant::now();
+
+            if now < slice_time {
+                thread::sleep(slice_time - now);
+                self.slice_sleep += slice_time - now;
+            } else {
+                timer::yield_now();
+            }
         } else {
             timer::yield_now();
         }
@@ -885,10 +1219,17 @@ impl EventHandler<ggez::GameError> for MyGame {
 
         if self.last_render_t != timer::ticks(ctx) {
 
-            let lerp_sub =
-                (timer::ticks(ctx) % CONSTS.tics_per_update) as f32 / CONSTS.tics_per_update as f32;
+            let lerp_sub = self.slice_index as f32 / self.resolution.tics_per_update() as f32;
+
+            let fresh_frame = self.slice_index == 0;
 
-            let fresh_frame = timer::ticks(ctx) % CONSTS.tics_per_update == 0;
+            if let Some(post_process) = &self.post_process {
+                post_process.begin(ctx);
+            }
+
+            if let Some(video_wall) = &self.video_wall {
+                video_wall.begin(ctx)?;
+            }
 
             for lerp_i in 0..CONSTS.cell_array_lerp_length {
                 let args = RenderArgs {
@@ -904,6 +1245,20 @@ impl EventHandler<ggez::GameError> for MyGame {
                 args.history_step().frame_renderer.draw(args)?;
             }
 
+            graphics::set_blend_mode(ctx, graphics::BlendMode::Alpha)?;
+
+            if let Some(video_wall) = &self.video_wall {
+                video_wall.end(ctx, self.post_process.as_ref().map(PostProcess::frame))?;
+            }
+
+            if let Some(post_process) = &self.post_process {
+                post_process.draw(ctx, &self.average_update_stat, self.mic_spectrograms.as_ref())?;
+            }
+
+            if self.library_browser.is_open() {
+                self.library_browser.draw(ctx, &self.blank_texture)?;
+            }
+
             self.last_render_t = timer::ticks(ctx);
             graphics::present(ctx)?;
         }
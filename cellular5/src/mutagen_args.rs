@@ -195,6 +195,27 @@ impl<'a> ComArg<'a> {
 
         new
     }
+
+    /// The cell in the history cell arrays under the current coordinates
+    pub fn history_coords(&self) -> (isize, isize) {
+        (
+            (self.coordinate_set.x.to_unsigned().into_inner() * self.history.width() as f32)
+                .round() as isize,
+            (self.coordinate_set.y.to_unsigned().into_inner() * self.history.height() as f32)
+                .round() as isize,
+        )
+    }
+
+    /// Gets a color from the last computed history step, wrapping at the edges
+    pub fn get_previous_color(&self, x: isize, y: isize) -> FloatColor {
+        self.history
+            .get(
+                x.rem_euclid(self.history.width() as isize) as usize,
+                y.rem_euclid(self.history.height() as isize) as usize,
+                self.current_t.saturating_sub(1),
+            )
+            .into()
+    }
 }
 
 impl<'a, 'b: 'a> Reborrow<'a, 'b, ComArg<'a>> for ComArg<'b> {
@@ -11,6 +11,7 @@ pub mod iterative_function_nodes;
 pub mod matrix_nodes;
 pub mod point_nodes;
 pub mod point_set_nodes;
+pub mod sdf_nodes;
 
 use crate::prelude::*;
 
@@ -75,7 +76,16 @@ pub mod mutagen_functions {
     }
 
     pub fn gamepad_node_weight<T: MutagenArg>(arg: T) -> f64 {
-        if arg.gamepads().gamepads.is_empty() {
+        //Touching a gamepad ends a screensaver, so nothing would ever drive these nodes
+        if CONSTS.screensaver || arg.gamepads().gamepads.is_empty() {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    pub fn mouse_node_weight<T: MutagenArg>(_arg: T) -> f64 {
+        if CONSTS.screensaver {
             0.0
         } else {
             1.0
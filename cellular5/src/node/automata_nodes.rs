@@ -6,11 +6,78 @@ use crate::prelude::*;
 #[derive(Generatable, UpdatableRecursively, Mutatable, Deserialize, Serialize, Debug)]
 #[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
 pub enum BinaryAutomataNodes {
+    //Cells brighter than half in the last history step are alive, over the Moore neighbourhood
+    #[mutagen(gen_weight = leaf_node_weight)]
+    PreviousFrameTotalistic { rule: OuterTotalisticRule },
+    #[mutagen(gen_weight = branch_node_weight)]
     Majority {
         child: NodeBox<BooleanNodes>,
         point_set: NodeBox<PointSetNodes>,
         child_normaliser: NodeBox<SFloatNormaliserNodes>,
     },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Parity {
+        child: NodeBox<BooleanNodes>,
+        point_set: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<SFloatNormaliserNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Threshold {
+        child: NodeBox<BooleanNodes>,
+        point_set: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<SFloatNormaliserNodes>,
+        child_threshold: NodeBox<UNFloatNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    OuterTotalistic {
+        rule: OuterTotalisticRule,
+        child: NodeBox<BooleanNodes>,
+        point_set: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<SFloatNormaliserNodes>,
+    },
+}
+
+/// Computes child at each offset in the point set, returning how many were true out of how many
+/// offsets there were. Offsets of zero are skipped when exclude_center is set.
+fn count_neighbours(
+    child: &NodeBox<BooleanNodes>,
+    point_set: &NodeBox<PointSetNodes>,
+    child_normaliser: &NodeBox<SFloatNormaliserNodes>,
+    exclude_center: bool,
+    mut compute_arg: ComArg,
+) -> (usize, usize) {
+    let offsets = point_set
+        .compute(compute_arg.reborrow())
+        .get_offsets(compute_arg.history.width(), compute_arg.history.height());
+    let normaliser = child_normaliser.compute(compute_arg.reborrow());
+
+    let mut true_count = 0;
+    let mut total = 0;
+
+    //this might blow up
+    for point in &offsets {
+        if exclude_center && point.into_inner().coords.norm() < f32::EPSILON {
+            continue;
+        }
+
+        let offset_arg = ComArg {
+            coordinate_set: compute_arg.coordinate_set.get_coord_shifted(
+                point.x(),
+                point.y(),
+                SNFloat::new(0.0),
+                normaliser,
+            ),
+            ..compute_arg.reborrow()
+        };
+
+        if child.compute(offset_arg).into_inner() {
+            true_count += 1;
+        }
+
+        total += 1;
+    }
+
+    (true_count, total)
 }
 
 impl Node for BinaryAutomataNodes {
@@ -20,35 +87,95 @@ impl Node for BinaryAutomataNodes {
         use BinaryAutomataNodes::*;
 
         match self {
+            PreviousFrameTotalistic { rule } => {
+                let (x, y) = compute_arg.history_coords();
+                let is_alive = |dx: isize, dy: isize| {
+                    compute_arg.get_previous_color(x + dx, y + dy).get_average() > 0.5
+                };
+
+                let mut live_neighbours = 0;
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if (dx, dy) != (0, 0) && is_alive(dx, dy) {
+                            live_neighbours += 1;
+                        }
+                    }
+                }
+
+                Boolean {
+                    value: rule.next_state(is_alive(0, 0), live_neighbours, 8),
+                }
+            }
             Majority {
                 child,
                 point_set,
                 child_normaliser,
             } => {
-                let mut true_count = 0;
-                let offsets = point_set
-                    .compute(compute_arg.reborrow())
-                    .get_offsets(CONSTS.cell_array_width, CONSTS.cell_array_height);
-
-                //this might blow up
-                for point in &offsets {
-                    let offset_arg = ComArg {
-                        coordinate_set: compute_arg.coordinate_set.get_coord_shifted(
-                            point.x(),
-                            point.y(),
-                            SNFloat::new(0.0),
-                            child_normaliser.compute(compute_arg.reborrow()),
-                        ),
-                        ..compute_arg.reborrow()
-                    };
-
-                    if child.compute(offset_arg).into_inner() {
-                        true_count += 1;
-                    }
+                let (true_count, total) = count_neighbours(
+                    child,
+                    point_set,
+                    child_normaliser,
+                    false,
+                    compute_arg.reborrow(),
+                );
+
+                Boolean {
+                    value: true_count > total / 2,
+                }
+            }
+            Parity {
+                child,
+                point_set,
+                child_normaliser,
+            } => {
+                let (true_count, _) = count_neighbours(
+                    child,
+                    point_set,
+                    child_normaliser,
+                    false,
+                    compute_arg.reborrow(),
+                );
+
+                Boolean {
+                    value: true_count % 2 == 1,
                 }
+            }
+            Threshold {
+                child,
+                point_set,
+                child_normaliser,
+                child_threshold,
+            } => {
+                let threshold = child_threshold.compute(compute_arg.reborrow()).into_inner();
+                let (true_count, total) = count_neighbours(
+                    child,
+                    point_set,
+                    child_normaliser,
+                    false,
+                    compute_arg.reborrow(),
+                );
+
+                Boolean {
+                    value: total > 0 && true_count as f32 / total as f32 >= threshold,
+                }
+            }
+            OuterTotalistic {
+                rule,
+                child,
+                point_set,
+                child_normaliser,
+            } => {
+                let alive = child.compute(compute_arg.reborrow()).into_inner();
+                let (true_count, total) = count_neighbours(
+                    child,
+                    point_set,
+                    child_normaliser,
+                    true,
+                    compute_arg.reborrow(),
+                );
 
                 Boolean {
-                    value: true_count > offsets.len() / 2,
+                    value: rule.next_state(alive, true_count, total),
                 }
             }
         }
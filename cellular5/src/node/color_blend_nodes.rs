@@ -12,7 +12,7 @@ pub enum ColorBlendNodes {
     #[mutagen(gen_weight = pipe_node_weight)]
     Invert { child: NodeBox<FloatColorNodes> },
 
-    #[mutagen(gen_weight = 0.0)] //branch_node_weight)]
+    #[mutagen(gen_weight = branch_node_weight)]
     Dissolve {
         color_a: NodeBox<FloatColorNodes>,
         color_b: NodeBox<FloatColorNodes>,
@@ -30,29 +30,74 @@ pub enum ColorBlendNodes {
         color_b: NodeBox<FloatColorNodes>,
     },
 
-    // #[mutagen(gen_weight = branch_node_weight)]
-    // ColorDodge {
-    //     color_a:NodeBox<FloatColorNodes>,
-    //     color_b:NodeBox<FloatColorNodes>,
-    //     value:NodeBox<UNFloatNodes>,
-    // },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Multiply {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
 
-    // #[mutagen(gen_weight = branch_node_weight)]
-    // LinearDodge {
-    //     color_a:NodeBox<FloatColorNodes>,
-    //     color_b:NodeBox<FloatColorNodes>,
-    //     value:NodeBox<UNFloatNodes>,
-    // },
+    #[mutagen(gen_weight = branch_node_weight)]
+    ColorDodge {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
 
-    // #[mutagen(gen_weight = branch_node_weight)]
-    // Multiply {
-    //     color_a:NodeBox<FloatColorNodes>,
-    //     color_b:NodeBox<FloatColorNodes>,
-    //     value:NodeBox<UNFloatNodes>,
-    // },
+    #[mutagen(gen_weight = branch_node_weight)]
+    ColorBurn {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    HardLight {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    SoftLight {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Difference {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Exclusion {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Hue {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Saturation {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Color {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Luminosity {
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
 
     // #[mutagen(gen_weight = branch_node_weight)]
-    // ColorBurn {
+    // LinearDodge {
     //     color_a:NodeBox<FloatColorNodes>,
     //     color_b:NodeBox<FloatColorNodes>,
     //     value:NodeBox<UNFloatNodes>,
@@ -147,58 +192,72 @@ impl Node for ColorBlendNodes {
                     color_b.compute(compute_arg.reborrow())
                 }
             }
-            Overlay { color_a, color_b } => {
-                let a = color_a.compute(compute_arg.reborrow());
-                let ar = a.r.into_inner();
-                let ag = a.g.into_inner();
-                let ab = a.b.into_inner();
-
-                let b = color_b.compute(compute_arg.reborrow());
-                let br = b.r.into_inner();
-                let bg = b.g.into_inner();
-                let bb = b.b.into_inner();
-
-                FloatColor {
-                    r: UNFloat::new(if ar < 0.5 {
-                        (2.0 * ar * br).max(1.0)
-                    } else {
-                        1.0 - (2.0 * ((1.0 - ar) * (1.0 - br)))
-                    }),
-                    g: UNFloat::new(if ag < 0.5 {
-                        (2.0 * ag * bg).max(1.0)
-                    } else {
-                        1.0 - (2.0 * ((1.0 - ag) * (1.0 - bg)))
-                    }),
-                    b: UNFloat::new(if ab < 0.5 {
-                        (2.0 * ab * bb).max(1.0)
-                    } else {
-                        1.0 - (2.0 * ((1.0 - ab) * (1.0 - bb)))
-                    }),
-                    a: UNFloat::ONE,
-                }
-            }
-            ScreenDodge { color_a, color_b } => {
-                let a = color_a.compute(compute_arg.reborrow());
-                let ar = a.r.into_inner();
-                let ag = a.g.into_inner();
-                let ab = a.b.into_inner();
-
-                let b = color_b.compute(compute_arg.reborrow());
-                let br = b.r.into_inner();
-                let bg = b.g.into_inner();
-                let bb = b.b.into_inner();
-
-                FloatColor {
-                    r: UNFloat::new(1.0 - ((1.0 - ar) * (1.0 - br))),
-                    g: UNFloat::new(1.0 - ((1.0 - ag) * (1.0 - bg))),
-                    b: UNFloat::new(1.0 - ((1.0 - ab) * (1.0 - bb))),
-                    a: UNFloat::ONE,
-                }
-            }
-            // ColorDodge {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
+            Overlay { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                overlay_channel,
+            ),
+            ScreenDodge { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                screen_channel,
+            ),
+            Multiply { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                multiply_channel,
+            ),
+            ColorDodge { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                color_dodge_channel,
+            ),
+            ColorBurn { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                color_burn_channel,
+            ),
+            HardLight { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                hard_light_channel,
+            ),
+            SoftLight { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                soft_light_channel,
+            ),
+            Difference { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                difference_channel,
+            ),
+            Exclusion { color_a, color_b } => blend_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                exclusion_channel,
+            ),
+            Hue { color_a, color_b } => blend_non_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                hue_blend,
+            ),
+            Saturation { color_a, color_b } => blend_non_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                saturation_blend,
+            ),
+            Color { color_a, color_b } => blend_non_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                color_blend,
+            ),
+            Luminosity { color_a, color_b } => blend_non_separable(
+                color_a.compute(compute_arg.reborrow()),
+                color_b.compute(compute_arg.reborrow()),
+                luminosity_blend,
+            ),
             // LinearDodge {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
-            // Multiply {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
-            // ColorBurn {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
             // LinearBurn {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
             // VividLight {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
             // LinearLight {color_a, color_b, value} => {if UNFloat::generate().into_inner() < value.compute(compute_arg.reborrow()).into_inner() {color_a.compute(compute_arg.reborrow())}else{color_b.compute(compute_arg.reborrow())}},
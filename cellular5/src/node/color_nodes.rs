@@ -209,10 +209,8 @@ pub enum FloatColorNodes {
         #[serde(skip)]
         #[mutagen(skip)]
         buffer: Buffer<FloatColor>,
-        // TODO Replace child_theta and child_rho with a polar coordinate node when they're implemented
-        theta: Angle,
+        polar: SNPolar,
         theta_delta: Angle,
-        rho: UNFloat,
         child_color: NodeBox<FloatColorNodes>,
     },
 
@@ -221,9 +219,7 @@ pub enum FloatColorNodes {
         #[serde(skip)]
         #[mutagen(skip)]
         buffer: Buffer<FloatColor>,
-        // TODO Replace child_theta and child_rho with a polar coordinate node when they're implemented
-        child_theta: NodeBox<AngleNodes>,
-        child_rho: NodeBox<UNFloatNodes>,
+        child_polar: NodeBox<SNPolarNodes>,
         child_normaliser: NodeBox<SFloatNormaliserNodes>,
         child_color: NodeBox<FloatColorNodes>,
         point: SNPoint,
@@ -249,6 +245,60 @@ pub enum FloatColorNodes {
         child_index: NodeBox<ByteNodes>,
         child_color: NodeBox<FloatColorNodes>,
     },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    GrayScott {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        field: GrayScottField,
+        feed: NodeBox<UNFloatNodes>,
+        kill: NodeBox<UNFloatNodes>,
+        diffusion: NodeBox<UNFloatNodes>,
+        color_a: NodeBox<FloatColorNodes>,
+        color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Lenia {
+        radius: Nibble,
+        mu: UNFloat,
+        sigma: UNFloat,
+        dt: UNFloat,
+    },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Convolve3x3 { kernel: Kernel3x3 },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Convolve5x5 { kernel: Kernel5x5 },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Turmites {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        colony: TurmiteColony,
+        rule: TurmiteRule,
+        agent_count: Nibble,
+        steps_per_update: Nibble,
+        child_color_a: NodeBox<FloatColorNodes>,
+        child_color_b: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    LSystemBuffer {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        buffer: Buffer<FloatColor>,
+        lsystem: LSystem,
+        child_angle: NodeBox<AngleNodes>,
+        child_color: NodeBox<FloatColorNodes>,
+    },
+
+    #[mutagen(gen_weight = pipe_node_weight)]
+    GradientMap {
+        palette: Palette,
+        child: NodeBox<UNFloatNodes>,
+    },
 }
 
 impl Node for FloatColorNodes {
@@ -912,6 +962,29 @@ impl Node for FloatColorNodes {
             NextPointLineBuffer { buffer, .. } => {
                 buffer[compute_arg.coordinate_set.get_coord_point()]
             }
+            GrayScott {
+                field,
+                color_a,
+                color_b,
+                ..
+            } => {
+                let v = field.get_v(compute_arg.coordinate_set.get_coord_point());
+
+                color_a
+                    .compute(compute_arg.reborrow())
+                    .lerp(color_b.compute(compute_arg.reborrow()), v)
+            }
+            Lenia {
+                radius,
+                mu,
+                sigma,
+                dt,
+            } => lenia_step_color(&compute_arg, &LeniaParams::new(*radius, *mu, *sigma, *dt)),
+            Convolve3x3 { kernel } => kernel.convolve_color(&compute_arg),
+            Convolve5x5 { kernel } => kernel.convolve_color(&compute_arg),
+            Turmites { colony, .. } => colony.get(compute_arg.coordinate_set.get_coord_point()),
+            LSystemBuffer { buffer, .. } => buffer[compute_arg.coordinate_set.get_coord_point()],
+            GradientMap { palette, child } => palette.sample(child.compute(compute_arg.reborrow())),
         }
     }
 }
@@ -1022,50 +1095,33 @@ impl<'a> Updatable<'a> for FloatColorNodes {
             // }
             IterativeCenteredPolarLineBuffer {
                 buffer,
-                ref mut theta,
+                ref mut polar,
                 theta_delta,
-                rho,
                 child_color,
             } => {
-                let new_theta = *theta + *theta_delta;
+                let new_polar = polar.rotate(*theta_delta);
                 let color = child_color.compute(arg.reborrow().into());
 
-                let point = SNPoint::from_snfloats(
-                    SNFloat::new(rho.into_inner() * f32::sin(theta.into_inner())),
-                    SNFloat::new(rho.into_inner() * f32::cos(theta.into_inner())),
-                );
+                buffer.draw_line(polar.to_snpoint(), new_polar.to_snpoint(), color);
 
-                // TODO rewrite this once we have a polar type node
-                let new_point = SNPoint::from_snfloats(
-                    SNFloat::new(rho.into_inner() * f32::sin(new_theta.into_inner())),
-                    SNFloat::new(rho.into_inner() * f32::cos(new_theta.into_inner())),
-                );
-
-                buffer.draw_line(point, new_point, color);
-
-                *theta = new_theta;
+                *polar = new_polar;
             }
 
             IterativePolarLineBuffer {
                 buffer,
-                child_theta,
-                child_rho,
+                child_polar,
                 child_normaliser,
                 child_color,
                 ref mut point,
                 ref mut theta,
             } => {
-                let new_theta = *theta + child_theta.compute(arg.reborrow().into());
-                let rho = child_rho.compute(arg.reborrow().into());
+                let polar = child_polar.compute(arg.reborrow().into());
+                let new_theta = *theta + polar.theta();
                 let normaliser = child_normaliser.compute(arg.reborrow().into());
                 let color = child_color.compute(arg.reborrow().into());
 
-                // TODO rewrite this once we have a polar type node
                 let new_point = point.normalised_add(
-                    SNPoint::from_snfloats(
-                        SNFloat::new(rho.into_inner() * f32::sin(new_theta.into_inner())),
-                        SNFloat::new(rho.into_inner() * f32::cos(new_theta.into_inner())),
-                    ),
+                    SNPolar::new(new_theta, polar.rho()).to_snpoint(),
                     normaliser,
                 );
 
@@ -1115,6 +1171,70 @@ impl<'a> Updatable<'a> for FloatColorNodes {
                 buffer.draw_line(source, dest, color);
             }
 
+            GrayScott {
+                field,
+                feed,
+                kill,
+                diffusion,
+                ..
+            } => {
+                let mut arg: ComArg<'a> = arg.into();
+
+                field.update(|point| {
+                    GrayScottParams::from_unfloats(
+                        feed.compute(arg.reborrow().replace_coords(&point)),
+                        kill.compute(arg.reborrow().replace_coords(&point)),
+                        diffusion.compute(arg.reborrow().replace_coords(&point)),
+                    )
+                });
+            }
+
+            Turmites {
+                colony,
+                rule,
+                agent_count,
+                steps_per_update,
+                child_color_a,
+                child_color_b,
+            } => {
+                let history = arg.history;
+                let current_t = arg.current_t;
+                let agent_count = (agent_count.into_inner() as usize + 1) * 4;
+                //Up to 128 steps, since patterns like Langton's highway take thousands to appear
+                let steps = 1 << (steps_per_update.into_inner() / 2);
+                let mut arg: ComArg<'a> = arg.into();
+
+                colony.update(rule, agent_count, steps, history, current_t, |point, level| {
+                    let mut arg = arg.reborrow().replace_coords(&point);
+
+                    child_color_a
+                        .compute(arg.reborrow())
+                        .lerp(child_color_b.compute(arg.reborrow()), level)
+                });
+            }
+
+            LSystemBuffer {
+                buffer,
+                lsystem,
+                child_angle,
+                child_color,
+            } => {
+                let lines = turtle_lines(
+                    lsystem.expanded(),
+                    child_angle.compute(arg.reborrow().into()),
+                );
+
+                //The whole drawing moves when the angle changes, so start from a blank buffer
+                *buffer = Buffer::default();
+
+                for (a, b) in lines {
+                    let color =
+                        child_color.compute(ComArg::from(arg.reborrow()).replace_coords(&a));
+
+                    buffer.draw_line(a, b, color);
+                }
+            }
+
             _ => {}
         }
     }
@@ -1203,6 +1323,18 @@ pub enum BitColorNodes {
         child_a: NodeBox<BitColorNodes>,
         child_b: NodeBox<BitColorNodes>,
     },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    Turmites {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        colony: TurmiteColony,
+        rule: TurmiteRule,
+        agent_count: Nibble,
+        steps_per_update: Nibble,
+        child_color_a: NodeBox<BitColorNodes>,
+        child_color_b: NodeBox<BitColorNodes>,
+    },
 }
 
 impl Node for BitColorNodes {
@@ -1264,10 +1396,10 @@ impl Node for BitColorNodes {
 
             NeighbourCountAutomata { rule } => {
                 let x = (compute_arg.coordinate_set.x.to_unsigned().into_inner()
-                    * CONSTS.cell_array_width as f32)
+                    * compute_arg.history.width() as f32)
                     .round() as isize;
                 let y = (compute_arg.coordinate_set.y.to_unsigned().into_inner()
-                    * CONSTS.cell_array_height as f32)
+                    * compute_arg.history.height() as f32)
                     .round() as isize;
                 let prev_t = compute_arg.current_t.saturating_sub(1);
 
@@ -1277,8 +1409,8 @@ impl Node for BitColorNodes {
 
                 for (dx, dy) in rule.neighbourhood.offsets() {
                     let [r, g, b] = BitColor::from(compute_arg.history.get(
-                        (x + dx).rem_euclid(CONSTS.cell_array_width as isize) as usize,
-                        (y + dy).rem_euclid(CONSTS.cell_array_height as isize) as usize,
+                        (x + dx).rem_euclid(compute_arg.history.width() as isize) as usize,
+                        (y + dy).rem_euclid(compute_arg.history.height() as isize) as usize,
                         prev_t,
                     ))
                     .to_components();
@@ -1301,10 +1433,10 @@ impl Node for BitColorNodes {
 
             LifeLikeAutomata { rule } => {
                 let x = (compute_arg.coordinate_set.x.to_unsigned().into_inner()
-                    * CONSTS.cell_array_width as f32)
+                    * compute_arg.history.width() as f32)
                     .round() as usize;
                 let y = (compute_arg.coordinate_set.y.to_unsigned().into_inner()
-                    * CONSTS.cell_array_height as f32)
+                    * compute_arg.history.height() as f32)
                     .round() as usize;
                 let prev_t = compute_arg.current_t.saturating_sub(1);
 
@@ -1317,8 +1449,9 @@ impl Node for BitColorNodes {
                 ) {
                     for (dx, dy) in rule.neighbourhood.offsets() {
                         let neighbour = BitColor::from(compute_arg.history.get(
-                            (x as isize + dx).rem_euclid(CONSTS.cell_array_width as isize) as usize,
-                            (y as isize + dy).rem_euclid(CONSTS.cell_array_height as isize)
+                            (x as isize + dx).rem_euclid(compute_arg.history.width() as isize)
+                                as usize,
+                            (y as isize + dy).rem_euclid(compute_arg.history.height() as isize)
                                 as usize,
                             prev_t,
                         ));
@@ -1354,10 +1487,10 @@ impl Node for BitColorNodes {
 
             CyclingLifeLikeAutomata { rule } => {
                 let x = (compute_arg.coordinate_set.x.to_unsigned().into_inner()
-                    * CONSTS.cell_array_width as f32)
+                    * compute_arg.history.width() as f32)
                     .round() as usize;
                 let y = (compute_arg.coordinate_set.y.to_unsigned().into_inner()
-                    * CONSTS.cell_array_height as f32)
+                    * compute_arg.history.height() as f32)
                     .round() as usize;
                 let prev_t = compute_arg.current_t.saturating_sub(1);
 
@@ -1370,8 +1503,9 @@ impl Node for BitColorNodes {
                 ) {
                     for (dx, dy) in rule.neighbourhood.offsets() {
                         let neighbour = BitColor::from(compute_arg.history.get(
-                            (x as isize + dx).rem_euclid(CONSTS.cell_array_width as isize) as usize,
-                            (y as isize + dy).rem_euclid(CONSTS.cell_array_height as isize)
+                            (x as isize + dx).rem_euclid(compute_arg.history.width() as isize)
+                                as usize,
+                            (y as isize + dy).rem_euclid(compute_arg.history.height() as isize)
                                 as usize,
                             prev_t,
                         ));
@@ -1498,6 +1632,9 @@ impl Node for BitColorNodes {
                     child_b.compute(compute_arg.reborrow())
                 }
             }
+            Turmites { colony, .. } => {
+                colony.get(compute_arg.coordinate_set.get_coord_point()).into()
+            }
         }
     }
 }
@@ -1505,7 +1642,36 @@ impl Node for BitColorNodes {
 impl<'a> Updatable<'a> for BitColorNodes {
     type UpdateArg = UpdArg<'a>;
 
-    fn update(&mut self, _arg: UpdArg<'a>) {}
+    fn update(&mut self, arg: UpdArg<'a>) {
+        use BitColorNodes::*;
+
+        match self {
+            Turmites {
+                colony,
+                rule,
+                agent_count,
+                steps_per_update,
+                child_color_a,
+                child_color_b,
+            } => {
+                let history = arg.history;
+                let current_t = arg.current_t;
+                let agent_count = (agent_count.into_inner() as usize + 1) * 4;
+                //Up to 128 steps, since patterns like Langton's highway take thousands to appear
+                let steps = 1 << (steps_per_update.into_inner() / 2);
+                let mut arg: ComArg<'a> = arg.into();
+
+                colony.update(rule, agent_count, steps, history, current_t, |point, level| {
+                    let mut arg = arg.reborrow().replace_coords(&point);
+
+                    FloatColor::from(child_color_a.compute(arg.reborrow()))
+                        .lerp(FloatColor::from(child_color_b.compute(arg.reborrow())), level)
+                });
+            }
+
+            _ => {}
+        }
+    }
 }
 
 #[derive(Generatable, UpdatableRecursively, Mutatable, Serialize, Deserialize, Debug)]
@@ -1574,6 +1740,13 @@ pub enum ByteColorNodes {
         child_a: NodeBox<ByteColorNodes>,
         child_b: NodeBox<ByteColorNodes>,
     },
+
+    #[mutagen(gen_weight = pipe_node_weight)]
+    WorleyCell {
+        seed: Byte,
+        distance_function: DistanceFunction,
+        child_points: NodeBox<PointSetNodes>,
+    },
 }
 
 impl Node for ByteColorNodes {
@@ -1639,10 +1812,10 @@ impl Node for ByteColorNodes {
                 rule,
             } => {
                 let x = (compute_arg.coordinate_set.x.to_unsigned().into_inner()
-                    * CONSTS.cell_array_width as f32)
+                    * compute_arg.history.width() as f32)
                     .round() as usize;
                 let y = (compute_arg.coordinate_set.y.to_unsigned().into_inner()
-                    * CONSTS.cell_array_height as f32)
+                    * compute_arg.history.height() as f32)
                     .round() as usize;
                 let prev_t = compute_arg.current_t.saturating_sub(1);
 
@@ -1650,8 +1823,8 @@ impl Node for ByteColorNodes {
 
                 // for (dx, dy) in rule.neighbourhood.offsets() {
                 //     let neighbour = BitColor::from(compute_arg.history.get(
-                //         (x as isize + dx).rem_euclid(CONSTS.cell_array_width as isize) as usize,
-                //         (y as isize + dy).rem_euclid(CONSTS.cell_array_height as isize) as usize,
+                //         (x as isize + dx).rem_euclid(compute_arg.history.width() as isize) as usize,
+                //         (y as isize + dy).rem_euclid(compute_arg.history.height() as isize) as usize,
                 //         prev_t,
                 //     ));
 
@@ -1671,8 +1844,9 @@ impl Node for ByteColorNodes {
                 ) {
                     for (dx, dy) in rule.neighbourhood.offsets() {
                         let neighbour = BitColor::from(compute_arg.history.get(
-                            (x as isize + dx).rem_euclid(CONSTS.cell_array_width as isize) as usize,
-                            (y as isize + dy).rem_euclid(CONSTS.cell_array_height as isize)
+                            (x as isize + dx).rem_euclid(compute_arg.history.width() as isize)
+                                as usize,
+                            (y as isize + dy).rem_euclid(compute_arg.history.height() as isize)
                                 as usize,
                             prev_t,
                         ));
@@ -1756,6 +1930,22 @@ impl Node for ByteColorNodes {
                     child_b.compute(compute_arg.reborrow())
                 }
             }
+            WorleyCell {
+                seed,
+                distance_function,
+                child_points,
+            } => WorleyResult::new(
+                compute_arg.coordinate_set.get_coord_point(),
+                child_points.compute(compute_arg.reborrow()).points(),
+                distance_function,
+            )
+            .map(|result| result.cell_color(*seed))
+            .unwrap_or_else(|| ByteColor {
+                r: Byte::new(0),
+                g: Byte::new(0),
+                b: Byte::new(0),
+                a: Byte::new(255),
+            }),
         }
     }
 }
@@ -2063,6 +2253,12 @@ pub enum LABColorNodes {
         alpha: NodeBox<UNFloatNodes>,
     },
 
+    #[mutagen(gen_weight = branch_node_weight)]
+    EscapeTime {
+        child_escape_time: NodeBox<EscapeTimeNodes>,
+        alpha: NodeBox<UNFloatNodes>,
+    },
+
     #[mutagen(gen_weight = branch_node_weight)]
     ModifyState {
         child: NodeBox<LABColorNodes>,
@@ -2128,6 +2324,19 @@ impl Node for LABColorNodes {
                 }
             }
 
+            EscapeTime {
+                child_escape_time,
+                alpha,
+            } => {
+                let result = child_escape_time.compute(compute_arg.reborrow());
+
+                LABColor {
+                    l: result.smooth_iterations.to_signed(),
+                    ab: result.z_final,
+                    alpha: alpha.compute(compute_arg.reborrow()),
+                }
+            }
+
             ModifyState { child, child_state } => child.compute(ComArg {
                 coordinate_set: child_state.compute(compute_arg.reborrow()),
                 ..compute_arg.reborrow()
@@ -716,6 +716,88 @@ pub enum UNFloatNodes {
         buffer: Buffer<UNFloat>,
         current_index: SInt,
     },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    GrayScott {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        field: GrayScottField,
+        feed: NodeBox<UNFloatNodes>,
+        kill: NodeBox<UNFloatNodes>,
+        diffusion: NodeBox<UNFloatNodes>,
+    },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Lenia {
+        radius: Nibble,
+        mu: UNFloat,
+        sigma: UNFloat,
+        dt: UNFloat,
+    },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    EdgeMagnitude3x3,
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    EdgeMagnitude5x5,
+
+    #[mutagen(gen_weight = pipe_node_weight)]
+    FromEscapeTime { child: NodeBox<EscapeTimeNodes> },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    FromSdf {
+        child: NodeBox<SdfNodes>,
+        child_normaliser: NodeBox<UFloatNormaliserNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    WorleyF1 {
+        distance_function: DistanceFunction,
+        child_points: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<UFloatNormaliserNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    WorleyF2 {
+        distance_function: DistanceFunction,
+        child_points: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<UFloatNormaliserNodes>,
+    },
+
+    #[mutagen(gen_weight = branch_node_weight)]
+    WorleyEdge {
+        distance_function: DistanceFunction,
+        child_points: NodeBox<PointSetNodes>,
+        child_normaliser: NodeBox<UFloatNormaliserNodes>,
+    },
+
+    #[mutagen(gen_weight = pipe_node_weight)]
+    WorleyCellIndex {
+        distance_function: DistanceFunction,
+        child_points: NodeBox<PointSetNodes>,
+    },
+
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Physarum {
+        #[serde(skip)]
+        #[mutagen(skip)]
+        field: PhysarumField,
+        sensor_angle: Angle,
+        sensor_distance: UNFloat,
+        decay: UNFloat,
+    },
+}
+
+fn compute_worley(
+    mut compute_arg: ComArg,
+    distance_function: &DistanceFunction,
+    child_points: &NodeBox<PointSetNodes>,
+) -> Option<WorleyResult> {
+    WorleyResult::new(
+        compute_arg.coordinate_set.get_coord_point(),
+        child_points.compute(compute_arg.reborrow()).points(),
+        distance_function,
+    )
 }
 
 impl Node for UNFloatNodes {
@@ -1086,6 +1168,58 @@ impl Node for UNFloatNodes {
 
                 buffer[discrete_point]
             }
+            GrayScott { field, .. } => field.get_v(compute_arg.coordinate_set.get_coord_point()),
+            Lenia {
+                radius,
+                mu,
+                sigma,
+                dt,
+            } => lenia_step_unfloat(&compute_arg, &LeniaParams::new(*radius, *mu, *sigma, *dt)),
+            EdgeMagnitude3x3 => Kernel3x3::edge_magnitude(&compute_arg),
+            EdgeMagnitude5x5 => Kernel5x5::edge_magnitude(&compute_arg),
+            FromEscapeTime { child } => child.compute(compute_arg).smooth_iterations,
+            FromSdf {
+                child,
+                child_normaliser,
+            } => child_normaliser
+                .compute(compute_arg.reborrow())
+                .normalise(child.compute(compute_arg.reborrow()).into_inner()),
+            WorleyF1 {
+                distance_function,
+                child_points,
+                child_normaliser,
+            } => compute_worley(compute_arg.reborrow(), distance_function, child_points)
+                .map_or(UNFloat::ZERO, |result| {
+                    child_normaliser
+                        .compute(compute_arg.reborrow())
+                        .normalise(result.f1)
+                }),
+            WorleyF2 {
+                distance_function,
+                child_points,
+                child_normaliser,
+            } => compute_worley(compute_arg.reborrow(), distance_function, child_points)
+                .map_or(UNFloat::ZERO, |result| {
+                    child_normaliser
+                        .compute(compute_arg.reborrow())
+                        .normalise(result.f2)
+                }),
+            WorleyEdge {
+                distance_function,
+                child_points,
+                child_normaliser,
+            } => compute_worley(compute_arg.reborrow(), distance_function, child_points)
+                .map_or(UNFloat::ZERO, |result| {
+                    child_normaliser
+                        .compute(compute_arg.reborrow())
+                        .normalise(result.edge_distance())
+                }),
+            WorleyCellIndex {
+                distance_function,
+                child_points,
+            } => compute_worley(compute_arg.reborrow(), distance_function, child_points)
+                .map_or(UNFloat::ZERO, |result| result.cell_index()),
+            Physarum { field, .. } => field.get(compute_arg.coordinate_set.get_coord_point()),
         }
     }
 }
@@ -1213,6 +1347,28 @@ impl<'a> Updatable<'a> for UNFloatNodes {
 
                 *current_index = current_index.circular_add(SInt::new(1));
             }
+            GrayScott {
+                field,
+                feed,
+                kill,
+                diffusion,
+            } => {
+                let mut arg: ComArg<'a> = arg.into();
+
+                field.update(|point| {
+                    GrayScottParams::from_unfloats(
+                        feed.compute(arg.reborrow().replace_coords(&point)),
+                        kill.compute(arg.reborrow().replace_coords(&point)),
+                        diffusion.compute(arg.reborrow().replace_coords(&point)),
+                    )
+                });
+            }
+            Physarum {
+                field,
+                sensor_angle,
+                sensor_distance,
+                decay,
+            } => field.update(&PhysarumParams::new(*sensor_angle, *sensor_distance, *decay)),
             _ => {}
         }
     }
@@ -1,3 +1,5 @@
+use std::f32::consts::PI;
+
 use mutagen::{Generatable, Mutatable, Reborrow, Updatable, UpdatableRecursively};
 use na::{geometry::Point2, geometry::Rotation2};
 use serde::{Deserialize, Serialize};
@@ -119,6 +121,24 @@ pub enum CoordMapNodes {
         point_b: SNPoint,
     },
     #[mutagen(gen_weight = branch_node_weight)]
+    TessellateP4m {
+        child_a: NodeBox<SNPointNodes>,
+        child_b: NodeBox<SNPointNodes>,
+        child_scale: NodeBox<SNPointNodes>,
+        child_normaliser: NodeBox<SFloatNormaliserNodes>,
+        point_a: SNPoint,
+        point_b: SNPoint,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    TessellateP6m {
+        child_a: NodeBox<SNPointNodes>,
+        child_b: NodeBox<SNPointNodes>,
+        child_scale: NodeBox<SNPointNodes>,
+        child_normaliser: NodeBox<SFloatNormaliserNodes>,
+        point_a: SNPoint,
+        point_b: SNPoint,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
     TessellatePerPoint {
         child_a: NodeBox<SNPointNodes>,
         child_b: NodeBox<SNPointNodes>,
@@ -130,6 +150,133 @@ pub enum CoordMapNodes {
     },
     #[mutagen(gen_weight = pipe_node_weight)]
     TesellateClosestPointSet { child: NodeBox<PointSetNodes> },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Kaleidoscope {
+        segments: NodeBox<NibbleNodes>,
+        rotation: NodeBox<AngleNodes>,
+    },
+}
+
+/// Folds a point into a single mirrored wedge of an n-fold kaleidoscope, with the wedge's
+/// edges starting at the given rotation
+fn kaleidoscope_fold(p: Point2<f32>, segments: f32, rotation: f32) -> Point2<f32> {
+    let rho = p.coords.norm();
+    let wedge = 2.0 * PI / segments;
+
+    let mut theta = (p.y.atan2(p.x) - rotation).rem_euclid(wedge);
+    if theta > wedge * 0.5 {
+        theta = wedge - theta;
+    }
+
+    theta += rotation;
+
+    Point2::new(rho * theta.cos(), rho * theta.sin())
+}
+
+/// Repeats the rectangle between a and b across the plane, mirroring each copy into its
+/// neighbours so there are no seams
+fn tessellate(coordinate_set: CoordinateSet, a: Point2<f32>, b: Point2<f32>) -> CoordinateSet {
+    let w = b.x - a.x;
+    let h = b.y - a.y;
+
+    let mut x_scale = 2.0 / w;
+    if !x_scale.is_normal() {
+        x_scale = 10000.0;
+    }
+
+    let mut y_scale = 2.0 / h;
+    if !y_scale.is_normal() {
+        y_scale = 10000.0;
+    }
+
+    let xc = 0.5 * (a.x + b.x);
+    let yc = 0.5 * (a.y + b.y);
+
+    CoordinateSet {
+        x: SNFloat::new_triangle(
+            SNFloat::new_triangle(coordinate_set.x.into_inner() * x_scale + xc).into_inner()
+                * 0.5
+                * w
+                - xc,
+        ),
+        y: SNFloat::new_triangle(
+            SNFloat::new_triangle(coordinate_set.y.into_inner() * y_scale + yc).into_inner()
+                * 0.5
+                * h
+                - yc,
+        ),
+        t: coordinate_set.t,
+    }
+}
+
+/// Square tiling with the mirror lines of p4m. The diagonal mirror only lines up on a square, so
+/// the rectangle between a and b is evened out to a square of the same centre and mean side
+fn tessellate_p4m(coordinate_set: CoordinateSet, a: Point2<f32>, b: Point2<f32>) -> CoordinateSet {
+    let side = 0.5 * ((b.x - a.x).abs() + (b.y - a.y).abs());
+
+    let mut scale = 2.0 / side;
+    if !scale.is_normal() {
+        scale = 10000.0;
+    }
+
+    let xc = 0.5 * (a.x + b.x);
+    let yc = 0.5 * (a.y + b.y);
+
+    let x = SNFloat::new_triangle(coordinate_set.x.into_inner() * scale + xc)
+        .into_inner()
+        .abs();
+    let y = SNFloat::new_triangle(coordinate_set.y.into_inner() * scale + yc)
+        .into_inner()
+        .abs();
+
+    //Mirroring across both axes and the diagonal leaves the square's symmetry group
+    CoordinateSet {
+        x: SNFloat::new_triangle(x.max(y) * 0.5 * side - xc),
+        y: SNFloat::new_triangle(x.min(y) * 0.5 * side - yc),
+        t: coordinate_set.t,
+    }
+}
+
+/// Hexagonal tiling with the mirror lines of p6m. The distance between a and b sets the spacing
+/// between hexagon centres, and the result is the offset from the nearest centre folded into
+/// one twelfth of the hexagon, scaled so its corners land on 1
+fn tessellate_p6m(coordinate_set: CoordinateSet, a: Point2<f32>, b: Point2<f32>) -> CoordinateSet {
+    let spacing = (b - a).norm().max(0.01);
+    let sqrt_3 = 3.0_f32.sqrt();
+
+    let p = Point2::new(
+        coordinate_set.x.into_inner() - a.x,
+        coordinate_set.y.into_inner() - a.y,
+    ) / spacing;
+
+    //Axial coordinates on a lattice with basis (1, 0) and (1/2, sqrt(3)/2), rounded in cube space
+    let q = p.x - p.y / sqrt_3;
+    let r = 2.0 * p.y / sqrt_3;
+    let s = -q - r;
+
+    let (mut q_round, mut r_round, s_round) = (q.round(), r.round(), s.round());
+    let (q_diff, r_diff, s_diff) = (
+        (q_round - q).abs(),
+        (r_round - r).abs(),
+        (s_round - s).abs(),
+    );
+
+    if q_diff > r_diff && q_diff > s_diff {
+        q_round = -r_round - s_round;
+    } else if r_diff > s_diff {
+        r_round = -q_round - s_round;
+    }
+
+    let center = Point2::new(q_round + r_round * 0.5, r_round * sqrt_3 * 0.5);
+    let offset = Point2::from(p - center) * sqrt_3;
+
+    let folded = kaleidoscope_fold(offset, 6.0, 0.0);
+
+    CoordinateSet {
+        x: SNFloat::new_clamped(folded.x),
+        y: SNFloat::new_clamped(folded.y),
+        t: coordinate_set.t,
+    }
 }
 
 impl Node for CoordMapNodes {
@@ -463,89 +610,30 @@ impl Node for CoordMapNodes {
             }
             Tessellate {
                 point_a, point_b, ..
-            } => {
-                let a = point_a.into_inner();
-                let b = point_b.into_inner();
-
-                let w = b.x - a.x;
-                let h = b.y - a.y;
-
-                let mut x_scale = 2.0 / w;
-                if !x_scale.is_normal() {
-                    x_scale = 10000.0;
-                }
-
-                let mut y_scale = 2.0 / h;
-                if !y_scale.is_normal() {
-                    y_scale = 10000.0;
-                }
-
-                let xc = 0.5 * (a.x + b.x);
-                let yc = 0.5 * (a.y + b.y);
-
-                CoordinateSet {
-                    x: SNFloat::new_triangle(
-                        SNFloat::new_triangle(
-                            compute_arg.coordinate_set.x.into_inner() * x_scale + xc,
-                        )
-                        .into_inner()
-                            * 0.5
-                            * w
-                            - xc,
-                    ),
-                    y: SNFloat::new_triangle(
-                        SNFloat::new_triangle(
-                            compute_arg.coordinate_set.y.into_inner() * y_scale + yc,
-                        )
-                        .into_inner()
-                            * 0.5
-                            * h
-                            - yc,
-                    ),
-                    t: compute_arg.coordinate_set.t,
-                }
-            }
+            } => tessellate(
+                compute_arg.coordinate_set,
+                point_a.into_inner(),
+                point_b.into_inner(),
+            ),
+            TessellateP4m {
+                point_a, point_b, ..
+            } => tessellate_p4m(
+                compute_arg.coordinate_set,
+                point_a.into_inner(),
+                point_b.into_inner(),
+            ),
+            TessellateP6m {
+                point_a, point_b, ..
+            } => tessellate_p6m(
+                compute_arg.coordinate_set,
+                point_a.into_inner(),
+                point_b.into_inner(),
+            ),
             TessellatePerPoint { child_a, child_b } => {
                 let a = child_a.compute(compute_arg.reborrow()).into_inner();
                 let b = child_b.compute(compute_arg.reborrow()).into_inner();
 
-                let w = b.x - a.x;
-                let h = b.y - a.y;
-
-                let mut x_scale = 2.0 / w;
-                if !x_scale.is_normal() {
-                    x_scale = 10000.0;
-                }
-
-                let mut y_scale = 2.0 / h;
-                if !y_scale.is_normal() {
-                    y_scale = 10000.0;
-                }
-
-                let xc = 0.5 * (a.x + b.x);
-                let yc = 0.5 * (a.y + b.y);
-
-                CoordinateSet {
-                    x: SNFloat::new_triangle(
-                        SNFloat::new_triangle(
-                            compute_arg.coordinate_set.x.into_inner() * x_scale + xc,
-                        )
-                        .into_inner()
-                            * 0.5
-                            * w
-                            - xc,
-                    ),
-                    y: SNFloat::new_triangle(
-                        SNFloat::new_triangle(
-                            compute_arg.coordinate_set.y.into_inner() * y_scale + yc,
-                        )
-                        .into_inner()
-                            * 0.5
-                            * h
-                            - yc,
-                    ),
-                    t: compute_arg.coordinate_set.t,
-                }
+                tessellate(compute_arg.coordinate_set, a, b)
             }
 
             TesellateClosestPointSet { child } => {
@@ -600,6 +688,23 @@ impl Node for CoordMapNodes {
                     t: compute_arg.coordinate_set.t,
                 }
             }
+
+            Kaleidoscope { segments, rotation } => {
+                let segments = segments.compute(compute_arg.reborrow()).into_inner().max(1);
+                let rotation = rotation.compute(compute_arg.reborrow()).into_inner();
+
+                let folded = kaleidoscope_fold(
+                    compute_arg.coordinate_set.get_coord_point().into_inner(),
+                    f32::from(segments),
+                    rotation,
+                );
+
+                CoordinateSet {
+                    x: SNFloat::new_clamped(folded.x),
+                    y: SNFloat::new_clamped(folded.y),
+                    t: compute_arg.coordinate_set.t,
+                }
+            }
         }
     }
 }
@@ -618,6 +723,22 @@ impl<'a> Updatable<'a> for CoordMapNodes {
                 child_normaliser,
                 ref mut point_a,
                 ref mut point_b,
+            }
+            | CoordMapNodes::TessellateP4m {
+                child_a,
+                child_b,
+                child_scale,
+                child_normaliser,
+                ref mut point_a,
+                ref mut point_b,
+            }
+            | CoordMapNodes::TessellateP6m {
+                child_a,
+                child_b,
+                child_scale,
+                child_normaliser,
+                ref mut point_a,
+                ref mut point_b,
             } => {
                 let translation_scale = child_scale
                     .compute(arg.reborrow().into())
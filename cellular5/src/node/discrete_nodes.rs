@@ -103,16 +103,15 @@ pub enum BooleanNodes {
         rule: ElementaryAutomataRule,
         current_index: SInt,
     },
-    // #[mutagen(gen_weight = branch_node_weight)]
-    // Majority {
-    //     child:NodeBox<BooleanNodes>,
-    //     point_set: PointSet,
-    // },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    FromAutomata { child: NodeBox<BinaryAutomataNodes> },
     #[mutagen(gen_weight = gamepad_node_weight)]
     FromGamepadButton {
         button: GamepadButton,
         id: GamepadId,
     },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    InsideSdf { child: NodeBox<SdfNodes> },
 }
 
 impl Node for BooleanNodes {
@@ -250,39 +249,16 @@ impl Node for BooleanNodes {
                 buffer[discrete_point]
                 // buffer[compute_arg.coordinate_set.get_coord_point()]
             }
-            // Majority {
-            //     child,
-            //     point_set,
-            // } => {
-            //     let mut true_count = 0;
-            //     let offsets = point_set.get_offsets(CONSTS.cell_array_width, CONSTS.cell_array_height);
-
-            //     //this might blow up
-            //     for point in &offsets {
-            //         let offset_state = UpdateState {
-            //             coordinate_set: state.coordinate_set.get_coord_shifted(
-            //                 point.x(),
-            //                 point.y(),
-            //                 SNFloat::new(0.0),
-            //             ),
-            //             history: state.history,
-            //         };
-
-            //         if child.compute(offset_state).into_inner() {
-            //             true_count += 1;
-            //         }
-            //     }
-
-            //     Boolean {
-            //         value: true_count > offsets.len() / 2,
-            //     }
-            // }
+            FromAutomata { child } => child.compute(compute_arg.reborrow()),
             FromGamepadButton { button, id } => Boolean {
                 value: compute_arg.gamepads[*id]
                     .button_states
                     .get(*button)
                     .is_pressed,
             },
+            InsideSdf { child } => Boolean {
+                value: child.compute(compute_arg.reborrow()).is_inside(),
+            },
         }
     }
 }
@@ -376,6 +352,15 @@ pub enum NibbleNodes {
     // InvertNormalised { child:NodeBox<NibbleNodes> },
     #[mutagen(gen_weight = gamepad_node_weight)]
     FromGamepadFaceButtons { id: GamepadId },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    Fibonacci { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    PowerOfTwo { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = branch_node_weight)]
+    PingPong {
+        child_index: NodeBox<UIntNodes>,
+        child_range: NodeBox<NibbleNodes>,
+    },
 }
 
 impl Node for NibbleNodes {
@@ -452,6 +437,21 @@ impl Node for NibbleNodes {
                         | u8::from(gamepad.button_states.get(GamepadButton::South).is_pressed) << 3,
                 )
             }
+            Fibonacci { child_index } => Nibble::new(fibonacci_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                u64::from(Nibble::MODULUS),
+            ) as u8),
+            PowerOfTwo { child_index } => Nibble::new(power_of_two_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                u64::from(Nibble::MODULUS),
+            ) as u8),
+            PingPong {
+                child_index,
+                child_range,
+            } => Nibble::new(ping_pong(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                u64::from(child_range.compute(compute_arg.reborrow()).into_inner()),
+            ) as u8),
         }
     }
 }
@@ -528,6 +528,33 @@ pub enum ByteNodes {
         child_a: NodeBox<ByteNodes>,
         child_b: NodeBox<ByteNodes>,
     },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Fibonacci {
+        child_index: NodeBox<UIntNodes>,
+        child_modulus: NodeBox<ByteNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    PowerOfTwo {
+        child_index: NodeBox<UIntNodes>,
+        child_modulus: NodeBox<ByteNodes>,
+    },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    Prime { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    CollatzStoppingTime { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = branch_node_weight)]
+    PingPong {
+        child_index: NodeBox<UIntNodes>,
+        child_range: NodeBox<ByteNodes>,
+    },
+}
+
+/// A modulus of zero would make the sequence constant, so treat it as the full range of a byte
+fn byte_modulus(modulus: Byte) -> u64 {
+    match modulus.into_inner() {
+        0 => 256,
+        modulus => u64::from(modulus),
+    }
 }
 
 impl Node for ByteNodes {
@@ -606,6 +633,34 @@ impl Node for ByteNodes {
                     child_b.compute(compute_arg.reborrow())
                 }
             }
+            Fibonacci {
+                child_index,
+                child_modulus,
+            } => Byte::new(fibonacci_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                byte_modulus(child_modulus.compute(compute_arg.reborrow())),
+            ) as u8),
+            PowerOfTwo {
+                child_index,
+                child_modulus,
+            } => Byte::new(power_of_two_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                byte_modulus(child_modulus.compute(compute_arg.reborrow())),
+            ) as u8),
+            Prime { child_index } => Byte::new(nth_byte_prime(
+                child_index.compute(compute_arg.reborrow()).into_inner(),
+            )),
+            CollatzStoppingTime { child_index } => Byte::new(
+                collatz_stopping_time(child_index.compute(compute_arg.reborrow()).into_inner())
+                    .min(255) as u8,
+            ),
+            PingPong {
+                child_index,
+                child_range,
+            } => Byte::new(ping_pong(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                u64::from(child_range.compute(compute_arg.reborrow()).into_inner()),
+            ) as u8),
         }
     }
 }
@@ -651,6 +706,39 @@ pub enum UIntNodes {
         child_a: NodeBox<UIntNodes>,
         child_b: NodeBox<UIntNodes>,
     },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    FromCellX,
+    #[mutagen(gen_weight = leaf_node_weight)]
+    FromCellY,
+    #[mutagen(gen_weight = leaf_node_weight)]
+    FromCellIndex,
+    #[mutagen(gen_weight = branch_node_weight)]
+    Fibonacci {
+        child_index: NodeBox<UIntNodes>,
+        child_modulus: NodeBox<UIntNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    PowerOfTwo {
+        child_index: NodeBox<UIntNodes>,
+        child_modulus: NodeBox<UIntNodes>,
+    },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    Prime { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    CollatzStoppingTime { child_index: NodeBox<UIntNodes> },
+    #[mutagen(gen_weight = branch_node_weight)]
+    PingPong {
+        child_index: NodeBox<UIntNodes>,
+        child_range: NodeBox<UIntNodes>,
+    },
+}
+
+/// A modulus of zero is treated as the full range of a u32
+fn uint_modulus(modulus: UInt) -> u64 {
+    match modulus.into_inner() {
+        0 => 1 << 32,
+        modulus => u64::from(modulus),
+    }
 }
 
 impl Node for UIntNodes {
@@ -692,6 +780,41 @@ impl Node for UIntNodes {
                     child_b.compute(compute_arg.reborrow())
                 }
             }
+            FromCellX => UInt::new(compute_arg.history_coords().0.max(0) as u32),
+            FromCellY => UInt::new(compute_arg.history_coords().1.max(0) as u32),
+            FromCellIndex => {
+                let (x, y) = compute_arg.history_coords();
+                let width = compute_arg.history.width() as isize;
+
+                UInt::new((y * width + x).max(0) as u32)
+            }
+            Fibonacci {
+                child_index,
+                child_modulus,
+            } => UInt::new(fibonacci_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                uint_modulus(child_modulus.compute(compute_arg.reborrow())),
+            ) as u32),
+            PowerOfTwo {
+                child_index,
+                child_modulus,
+            } => UInt::new(power_of_two_mod(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                uint_modulus(child_modulus.compute(compute_arg.reborrow())),
+            ) as u32),
+            Prime { child_index } => {
+                UInt::new(nth_prime(child_index.compute(compute_arg.reborrow()).into_inner()))
+            }
+            CollatzStoppingTime { child_index } => UInt::new(collatz_stopping_time(
+                child_index.compute(compute_arg.reborrow()).into_inner(),
+            )),
+            PingPong {
+                child_index,
+                child_range,
+            } => UInt::new(ping_pong(
+                u64::from(child_index.compute(compute_arg.reborrow()).into_inner()),
+                u64::from(child_range.compute(compute_arg.reborrow()).into_inner()),
+            ) as u32),
         }
     }
 }
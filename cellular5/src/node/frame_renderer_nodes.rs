@@ -1,3 +1,4 @@
+use ggez::graphics::BlendMode;
 use mutagen::{Generatable, Mutatable, Reborrow, Updatable, UpdatableRecursively};
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +51,18 @@ pub enum FrameRendererNodes {
         from_scale_scalar_node: NodeBox<UNFloatNodes>,
         to_scale_scalar_node: NodeBox<UNFloatNodes>,
     },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    DisplacementFeedback {
+        child_strength: NodeBox<UNFloatNodes>,
+        flow_angle: Angle,
+    },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    ChannelSplit {
+        red_offset: SNPoint,
+        blue_offset: SNPoint,
+        child_spread: NodeBox<UNFloatNodes>,
+        twist: SNFloat,
+    },
     // #[mutagen(gen_weight = branch_node_weight)]
     //TODO: redo this so it doesn't kill people with epilepsy
     #[mutagen(gen_weight = 0.0)]
@@ -130,6 +143,24 @@ impl Node for FrameRendererNodes {
                 from_scale_scalar: from_scale_scalar_node.compute(compute_arg.reborrow()),
                 to_scale_scalar: to_scale_scalar_node.compute(compute_arg.reborrow()),
             },
+            FrameRendererNodes::DisplacementFeedback {
+                child_strength,
+                flow_angle,
+            } => FrameRenderers::DisplacementFeedback {
+                strength: child_strength.compute(compute_arg),
+                flow_angle: *flow_angle,
+            },
+            FrameRendererNodes::ChannelSplit {
+                red_offset,
+                blue_offset,
+                child_spread,
+                twist,
+            } => FrameRenderers::ChannelSplit {
+                red_offset: *red_offset,
+                blue_offset: *blue_offset,
+                spread: child_spread.compute(compute_arg),
+                twist: *twist,
+            },
             FrameRendererNodes::FadeAndChild {
                 child_renderer,
                 child_color,
@@ -150,3 +181,74 @@ impl<'a> Updatable<'a> for FrameRendererNodes {
 
     fn update(&mut self, _arg: UpdArg<'a>) {}
 }
+
+#[derive(Generatable, UpdatableRecursively, Mutatable, Deserialize, Serialize, Debug)]
+#[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
+pub enum BlendModeNodes {
+    #[mutagen(gen_weight = 10.0)]
+    Alpha,
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Add { interval: Nibble },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Subtract { interval: Nibble },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Multiply { interval: Nibble },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Lighten { interval: Nibble },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Darken { interval: Nibble },
+    #[mutagen(gen_weight = branch_node_weight)]
+    IfElse {
+        predicate: NodeBox<BooleanNodes>,
+        child_a: NodeBox<BlendModeNodes>,
+        child_b: NodeBox<BlendModeNodes>,
+    },
+}
+
+impl Node for BlendModeNodes {
+    type Output = FrameBlend;
+
+    fn compute(&self, mut compute_arg: ComArg) -> Self::Output {
+        use BlendModeNodes::*;
+
+        match self {
+            Alpha => FrameBlend::default(),
+            Add { interval } => FrameBlend::new(BlendMode::Add, interval.into_inner() as usize),
+            Subtract { interval } => {
+                FrameBlend::new(BlendMode::Subtract, interval.into_inner() as usize)
+            }
+            Multiply { interval } => {
+                FrameBlend::new(BlendMode::Multiply, interval.into_inner() as usize)
+            }
+            Lighten { interval } => {
+                FrameBlend::new(BlendMode::Lighten, interval.into_inner() as usize)
+            }
+            Darken { interval } => {
+                FrameBlend::new(BlendMode::Darken, interval.into_inner() as usize)
+            }
+            IfElse {
+                predicate,
+                child_a,
+                child_b,
+            } => {
+                if predicate.compute(compute_arg.reborrow()).into_inner() {
+                    child_a.compute(compute_arg.reborrow())
+                } else {
+                    child_b.compute(compute_arg.reborrow())
+                }
+            }
+        }
+    }
+}
+
+impl Default for BlendModeNodes {
+    fn default() -> Self {
+        BlendModeNodes::Alpha
+    }
+}
+
+impl<'a> Updatable<'a> for BlendModeNodes {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
@@ -34,6 +34,16 @@ pub enum IterativeFunctionNodes {
         child_normaliser: NodeBox<SFloatNormaliserNodes>,
         child_exit_normaliser: NodeBox<SFloatNormaliserNodes>,
     },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    FromEscapeTime { child: NodeBox<EscapeTimeNodes> },
+}
+
+/// Maps the current coordinates onto the complex plane
+fn plane_point(compute_arg: &ComArg, scale: Point2<f32>, offset: Point2<f32>) -> Complex<f64> {
+    Complex::new(
+        f64::from(2.0 * scale.x * (compute_arg.coordinate_set.x.into_inner() + offset.x)),
+        f64::from(2.0 * scale.y * (compute_arg.coordinate_set.y.into_inner() + offset.y)),
+    )
 }
 
 impl Node for IterativeFunctionNodes {
@@ -171,6 +181,7 @@ impl Node for IterativeFunctionNodes {
                     Byte::new(iterations),
                 )
             }
+            FromEscapeTime { child } => child.compute(compute_arg).to_iterative_result(),
         }
     }
 }
@@ -180,3 +191,121 @@ impl<'a> Updatable<'a> for IterativeFunctionNodes {
 
     fn update(&mut self, _arg: UpdArg<'a>) {}
 }
+
+#[derive(Generatable, UpdatableRecursively, Mutatable, Deserialize, Serialize, Debug)]
+#[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
+pub enum EscapeTimeNodes {
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Mandelbrot { power: Nibble, iterations: Byte },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Julia {
+        child_c: NodeBox<SNComplexNodes>,
+        child_power: NodeBox<NibbleNodes>,
+        child_scale: NodeBox<SNPointNodes>,
+        child_iterations: NodeBox<ByteNodes>,
+        child_exit_normaliser: NodeBox<SFloatNormaliserNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    BurningShip {
+        child_offset: NodeBox<SNPointNodes>,
+        child_scale: NodeBox<SNPointNodes>,
+        child_iterations: NodeBox<ByteNodes>,
+        child_exit_normaliser: NodeBox<SFloatNormaliserNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Tricorn {
+        child_offset: NodeBox<SNPointNodes>,
+        child_scale: NodeBox<SNPointNodes>,
+        child_iterations: NodeBox<ByteNodes>,
+        child_exit_normaliser: NodeBox<SFloatNormaliserNodes>,
+    },
+}
+
+impl Node for EscapeTimeNodes {
+    type Output = SmoothIterativeResult;
+
+    fn compute(&self, mut compute_arg: ComArg) -> Self::Output {
+        use EscapeTimeNodes::*;
+
+        match self {
+            Mandelbrot { power, iterations } => {
+                let power = 2 + u32::from(power.into_inner()) % 4;
+                let iterations = 1 + iterations.into_inner() as usize / 4;
+
+                //Shifted left so the main cardioid sits in the middle of the screen
+                let c = plane_point(&compute_arg, Point2::new(1.0, 1.0), Point2::new(-0.25, 0.0));
+
+                escape_time(
+                    EscapeTimeFormula::Multibrot { power },
+                    Complex::new(0.0, 0.0),
+                    c,
+                    iterations,
+                )
+                .to_smooth_result(SFloatNormaliser::SinRepeating)
+            }
+            Julia {
+                child_c,
+                child_power,
+                child_scale,
+                child_iterations,
+                child_exit_normaliser,
+            } => {
+                let c = child_c.compute(compute_arg.reborrow()).into_inner();
+                let power =
+                    2 + u32::from(child_power.compute(compute_arg.reborrow()).into_inner()) % 4;
+                let scale = child_scale.compute(compute_arg.reborrow()).into_inner();
+                let iterations = 1 + child_iterations
+                    .compute(compute_arg.reborrow())
+                    .into_inner() as usize
+                    / 4;
+
+                let z = plane_point(&compute_arg, scale, Point2::origin());
+
+                escape_time(EscapeTimeFormula::Multibrot { power }, z, c, iterations)
+                    .to_smooth_result(child_exit_normaliser.compute(compute_arg.reborrow()))
+            }
+            BurningShip {
+                child_offset,
+                child_scale,
+                child_iterations,
+                child_exit_normaliser,
+            } => {
+                let offset = child_offset.compute(compute_arg.reborrow()).into_inner();
+                let scale = child_scale.compute(compute_arg.reborrow()).into_inner();
+                let iterations = 1 + child_iterations
+                    .compute(compute_arg.reborrow())
+                    .into_inner() as usize
+                    / 4;
+
+                let c = plane_point(&compute_arg, scale, offset);
+
+                escape_time(EscapeTimeFormula::BurningShip, Complex::new(0.0, 0.0), c, iterations)
+                    .to_smooth_result(child_exit_normaliser.compute(compute_arg.reborrow()))
+            }
+            Tricorn {
+                child_offset,
+                child_scale,
+                child_iterations,
+                child_exit_normaliser,
+            } => {
+                let offset = child_offset.compute(compute_arg.reborrow()).into_inner();
+                let scale = child_scale.compute(compute_arg.reborrow()).into_inner();
+                let iterations = 1 + child_iterations
+                    .compute(compute_arg.reborrow())
+                    .into_inner() as usize
+                    / 4;
+
+                let c = plane_point(&compute_arg, scale, offset);
+
+                escape_time(EscapeTimeFormula::Tricorn, Complex::new(0.0, 0.0), c, iterations)
+                    .to_smooth_result(child_exit_normaliser.compute(compute_arg.reborrow()))
+            }
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for EscapeTimeNodes {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
@@ -14,7 +14,7 @@ pub enum SNPointNodes {
     Coordinate,
     #[mutagen(gen_weight = leaf_node_weight)]
     Constant { value: SNPoint },
-    #[mutagen(gen_weight = pipe_node_weight)]
+    #[mutagen(gen_weight = [pipe_node_weight, mouse_node_weight])]
     MouseCoords {
         child_normaliser: NodeBox<UFloatNormaliserNodes>,
     },
@@ -23,6 +23,8 @@ pub enum SNPointNodes {
         child_complex: NodeBox<SNComplexNodes>,
     },
     #[mutagen(gen_weight = pipe_node_weight)]
+    FromPolar { child: NodeBox<SNPolarNodes> },
+    #[mutagen(gen_weight = pipe_node_weight)]
     Invert { child: NodeBox<SNPointNodes> },
     #[mutagen(gen_weight = branch_node_weight)]
     FromSNFloats {
@@ -74,6 +76,7 @@ impl Node for SNPointNodes {
             FromComplex { child_complex } => {
                 SNPoint::from_complex(child_complex.compute(compute_arg))
             }
+            FromPolar { child } => child.compute(compute_arg.reborrow()).to_snpoint(),
             Invert { child } => {
                 let point = child.compute(compute_arg.reborrow()).into_inner();
                 SNPoint::new(Point2::new(point.x * -1.0, point.y * -1.0))
@@ -141,3 +144,74 @@ impl<'a> Updatable<'a> for SNPointNodes {
         }
     }
 }
+
+#[derive(Generatable, UpdatableRecursively, Mutatable, Deserialize, Serialize, Debug)]
+#[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
+pub enum SNPolarNodes {
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Coordinate,
+    #[mutagen(gen_weight = leaf_node_weight)]
+    Constant { value: SNPolar },
+    #[mutagen(gen_weight = pipe_node_weight)]
+    FromPoint { child: NodeBox<SNPointNodes> },
+    #[mutagen(gen_weight = branch_node_weight)]
+    FromAngleRho {
+        child_theta: NodeBox<AngleNodes>,
+        child_rho: NodeBox<UNFloatNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Spiral {
+        child_t: NodeBox<UNFloatNodes>,
+        child_turns: NodeBox<NibbleNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Rotate {
+        child: NodeBox<SNPolarNodes>,
+        child_angle: NodeBox<AngleNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    ScaleRho {
+        child: NodeBox<SNPolarNodes>,
+        child_scale: NodeBox<UNFloatNodes>,
+    },
+}
+
+impl Node for SNPolarNodes {
+    type Output = SNPolar;
+
+    fn compute(&self, mut compute_arg: ComArg) -> Self::Output {
+        use SNPolarNodes::*;
+
+        match self {
+            Coordinate => SNPolar::from_snpoint(compute_arg.coordinate_set.get_coord_point()),
+            Constant { value } => *value,
+            FromPoint { child } => SNPolar::from_snpoint(child.compute(compute_arg.reborrow())),
+            FromAngleRho {
+                child_theta,
+                child_rho,
+            } => SNPolar::new(
+                child_theta.compute(compute_arg.reborrow()),
+                child_rho.compute(compute_arg.reborrow()),
+            ),
+            Spiral {
+                child_t,
+                child_turns,
+            } => SNPolar::spiral(
+                child_t.compute(compute_arg.reborrow()),
+                f32::from(child_turns.compute(compute_arg.reborrow()).into_inner() + 1),
+            ),
+            Rotate { child, child_angle } => child
+                .compute(compute_arg.reborrow())
+                .rotate(child_angle.compute(compute_arg.reborrow())),
+            ScaleRho { child, child_scale } => child
+                .compute(compute_arg.reborrow())
+                .scale_rho(child_scale.compute(compute_arg.reborrow())),
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for SNPolarNodes {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
@@ -84,10 +84,8 @@ pub enum PointSetNodes {
     IterativePolarLine {
         #[mutagen(skip)]
         value: PointSet,
-        // TODO Replace child_theta and child_rho with a polar coordinate node when they're implemented
         child_n: NodeBox<ByteNodes>,
-        child_theta: NodeBox<AngleNodes>,
-        child_rho: NodeBox<UNFloatNodes>,
+        child_polar: NodeBox<SNPolarNodes>,
         child_normaliser: NodeBox<SFloatNormaliserNodes>,
     },
     #[mutagen(gen_weight = branch_node_weight)]
@@ -102,6 +100,25 @@ pub enum PointSetNodes {
         child_n: NodeBox<ByteNodes>,
         child_point: NodeBox<SNPointNodes>,
     },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Flock {
+        value: PointSet,
+        #[serde(skip)]
+        #[mutagen(skip)]
+        velocities: Vec<Vector2<f32>>,
+        child_separation: NodeBox<UNFloatNodes>,
+        child_alignment: NodeBox<UNFloatNodes>,
+        child_cohesion: NodeBox<UNFloatNodes>,
+    },
+}
+
+/// The corners of a regular polygon centered on the origin, repeating the first corner at the end
+pub fn polygon_points(edges: u8, radius: f32) -> Vec<SNPoint> {
+    let ratio = 1.0 / edges as f32;
+
+    (0..=edges)
+        .map(|i| SNPoint::new(Point2::new((ratio * i as f32) * 2.0 - 1.0, radius)).from_polar())
+        .collect()
 }
 
 impl Node for PointSetNodes {
@@ -125,6 +142,7 @@ impl Node for PointSetNodes {
             IterativePolarLine { value, .. } => value.clone(),
             RecomputedQueue { value, .. } => value.clone(),
             IterativeQueue { value, .. } => value.clone(),
+            Flock { value, .. } => value.clone(),
         }
     }
 }
@@ -288,18 +306,8 @@ impl<'a> Updatable<'a> for PointSetNodes {
             } => {
                 let edges = child_edges.compute(arg.reborrow().into()).into_inner() + 2;
                 let radius = child_radius.compute(arg.reborrow().into()).into_inner();
-                let mut edge_vec = Vec::new();
-
-                for i in 0..=edges {
-                    let ratio = 1.0 / edges as f32;
-
-                    edge_vec.push(
-                        SNPoint::new(Point2::new((ratio * i as f32) * 2.0 - 1.0, radius))
-                            .from_polar(),
-                    );
-                }
 
-                value.replace(Arc::new(edge_vec));
+                value.replace(Arc::new(polygon_points(edges, radius)));
             }
             //TODO: Something is funky here, give it a second pass.
             //TODO: Maybe swap this out for a matrix point grid
@@ -473,13 +481,13 @@ impl<'a> Updatable<'a> for PointSetNodes {
             PointSetNodes::IterativePolarLine {
                 ref mut value,
                 child_n,
-                child_rho,
-                child_theta,
+                child_polar,
                 child_normaliser,
             } => {
                 let n = child_n.compute(arg.reborrow().into()).into_inner().max(1);
-                let rho = child_rho.compute(arg.reborrow().into()).into_inner() / n as f32;
-                let theta_diff = child_theta.compute(arg.reborrow().into());
+                let polar = child_polar.compute(arg.reborrow().into());
+                let rho = UNFloat::new(polar.rho().into_inner() / n as f32);
+                let theta_diff = polar.theta();
 
                 value.replace(Arc::new(
                     (0..n)
@@ -490,10 +498,7 @@ impl<'a> Updatable<'a> for PointSetNodes {
                                 let normaliser = child_normaliser.compute(arg.reborrow().into());
 
                                 let new_point = point.normalised_add(
-                                    SNPoint::from_snfloats(
-                                        SNFloat::new(rho * f32::sin(new_theta.into_inner())),
-                                        SNFloat::new(rho * f32::cos(new_theta.into_inner())),
-                                    ),
+                                    SNPolar::new(new_theta, rho).to_snpoint(),
                                     normaliser,
                                 );
 
@@ -551,6 +556,28 @@ impl<'a> Updatable<'a> for PointSetNodes {
                 value.replace(Arc::new(new_points));
             }
 
+            PointSetNodes::Flock {
+                ref mut value,
+                velocities,
+                child_separation,
+                child_alignment,
+                child_cohesion,
+            } => {
+                let compute_arg = ComArg::from(arg.reborrow());
+
+                let new_points = step_flock(value.points(), velocities, |point| {
+                    let mut compute_arg = compute_arg.clone().replace_coords(&point);
+
+                    FlockWeights::from_unfloats(
+                        child_separation.compute(compute_arg.reborrow()),
+                        child_alignment.compute(compute_arg.reborrow()),
+                        child_cohesion.compute(compute_arg.reborrow()),
+                    )
+                });
+
+                value.replace(Arc::new(new_points));
+            }
+
             _ => {}
         }
     }
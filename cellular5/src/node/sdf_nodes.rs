@@ -0,0 +1,177 @@
+use mutagen::{Generatable, Mutatable, Reborrow, Updatable, UpdatableRecursively};
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+#[derive(Generatable, UpdatableRecursively, Mutatable, Deserialize, Serialize, Debug)]
+#[mutagen(gen_arg = type GenArg<'a>, mut_arg = type MutArg<'a>)]
+pub enum SdfNodes {
+    #[mutagen(gen_weight = leaf_node_weight)]
+    ConstantCircle { center: SNPoint, radius: UNFloat },
+    #[mutagen(gen_weight = leaf_node_weight)]
+    ConstantRectangle { center: SNPoint, size: SNPoint },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Circle {
+        child_center: NodeBox<SNPointNodes>,
+        child_radius: NodeBox<UNFloatNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Rectangle {
+        child_center: NodeBox<SNPointNodes>,
+        child_size: NodeBox<SNPointNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Segment {
+        child_a: NodeBox<SNPointNodes>,
+        child_b: NodeBox<SNPointNodes>,
+        child_thickness: NodeBox<UNFloatNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Polygon {
+        child_center: NodeBox<SNPointNodes>,
+        child_radius: NodeBox<UNFloatNodes>,
+        child_edges: NodeBox<NibbleNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Star {
+        child_center: NodeBox<SNPointNodes>,
+        child_radius: NodeBox<UNFloatNodes>,
+        child_inner_ratio: NodeBox<UNFloatNodes>,
+        child_points: NodeBox<NibbleNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Union {
+        child_a: NodeBox<SdfNodes>,
+        child_b: NodeBox<SdfNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Intersection {
+        child_a: NodeBox<SdfNodes>,
+        child_b: NodeBox<SdfNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    Subtraction {
+        child_a: NodeBox<SdfNodes>,
+        child_b: NodeBox<SdfNodes>,
+    },
+    #[mutagen(gen_weight = branch_node_weight)]
+    SmoothUnion {
+        child_a: NodeBox<SdfNodes>,
+        child_b: NodeBox<SdfNodes>,
+        child_smoothing: NodeBox<UNFloatNodes>,
+    },
+}
+
+impl Node for SdfNodes {
+    type Output = SignedDistance;
+
+    fn compute(&self, mut compute_arg: ComArg) -> Self::Output {
+        use SdfNodes::*;
+
+        let p = compute_arg.coordinate_set.get_coord_point().into_inner();
+
+        match self {
+            ConstantCircle { center, radius } => {
+                sd_circle(p, center.into_inner(), radius.into_inner())
+            }
+            ConstantRectangle { center, size } => {
+                sd_box(p, center.into_inner(), size.into_inner().coords)
+            }
+            Circle {
+                child_center,
+                child_radius,
+            } => sd_circle(
+                p,
+                child_center.compute(compute_arg.reborrow()).into_inner(),
+                child_radius.compute(compute_arg.reborrow()).into_inner(),
+            ),
+            Rectangle {
+                child_center,
+                child_size,
+            } => sd_box(
+                p,
+                child_center.compute(compute_arg.reborrow()).into_inner(),
+                child_size
+                    .compute(compute_arg.reborrow())
+                    .into_inner()
+                    .coords,
+            ),
+            Segment {
+                child_a,
+                child_b,
+                child_thickness,
+            } => {
+                let distance = sd_segment(
+                    p,
+                    child_a.compute(compute_arg.reborrow()).into_inner(),
+                    child_b.compute(compute_arg.reborrow()).into_inner(),
+                );
+                let thickness = child_thickness.compute(compute_arg.reborrow()).into_inner() * 0.25;
+
+                SignedDistance::new(distance.into_inner() - thickness)
+            }
+            Polygon {
+                child_center,
+                child_radius,
+                child_edges,
+            } => {
+                let center = child_center.compute(compute_arg.reborrow()).into_inner();
+                let radius = child_radius.compute(compute_arg.reborrow()).into_inner();
+                let edges = child_edges.compute(compute_arg.reborrow()).into_inner() + 3;
+
+                let vertices: Vec<_> = polygon_points(edges, radius)
+                    .into_iter()
+                    .map(|vertex| center + vertex.into_inner().coords)
+                    .collect();
+
+                sd_polygon(p, &vertices)
+            }
+            Star {
+                child_center,
+                child_radius,
+                child_inner_ratio,
+                child_points,
+            } => {
+                let center = child_center.compute(compute_arg.reborrow()).into_inner();
+                let radius = child_radius.compute(compute_arg.reborrow()).into_inner();
+                let inner_ratio = child_inner_ratio.compute(compute_arg.reborrow()).into_inner();
+                let points = child_points.compute(compute_arg.reborrow()).into_inner() + 3;
+
+                // A polygon with twice the corners, with every other corner pulled inwards
+                let vertices: Vec<_> = polygon_points(points * 2, radius)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, vertex)| {
+                        let scale = if i % 2 == 0 { 1.0 } else { inner_ratio };
+                        center + vertex.into_inner().coords * scale
+                    })
+                    .collect();
+
+                sd_polygon(p, &vertices)
+            }
+            Union { child_a, child_b } => child_a
+                .compute(compute_arg.reborrow())
+                .union(child_b.compute(compute_arg.reborrow())),
+            Intersection { child_a, child_b } => child_a
+                .compute(compute_arg.reborrow())
+                .intersection(child_b.compute(compute_arg.reborrow())),
+            Subtraction { child_a, child_b } => child_a
+                .compute(compute_arg.reborrow())
+                .subtraction(child_b.compute(compute_arg.reborrow())),
+            SmoothUnion {
+                child_a,
+                child_b,
+                child_smoothing,
+            } => child_a.compute(compute_arg.reborrow()).smooth_union(
+                child_b.compute(compute_arg.reborrow()),
+                child_smoothing.compute(compute_arg.reborrow()).into_inner() * 0.5,
+            ),
+        }
+    }
+}
+
+impl<'a> Updatable<'a> for SdfNodes {
+    type UpdateArg = UpdArg<'a>;
+
+    fn update(&mut self, _arg: UpdArg<'a>) {}
+}
@@ -5,7 +5,11 @@ use serde::{Deserialize, Serialize};
 use crate::{arena_wrappers::*, prelude::*, ArenaSlot};
 
 #[derive(Default, Debug, UpdatableRecursively, Serialize, Deserialize)]
+//Node families added since a genome was saved just start out empty
+#[serde(default)]
 pub struct NodeSet {
+    //automata
+    binary_automata_nodes: Metarena<BinaryAutomataNodes>,
     //color_blend
     color_blend_nodes: Metarena<ColorBlendNodes>,
     //color
@@ -32,10 +36,12 @@ pub struct NodeSet {
     snfloat_matrix3_nodes: Metarena<SNFloatMatrix3Nodes>,
     //point
     snpoint_nodes: Metarena<SNPointNodes>,
+    snpolar_nodes: Metarena<SNPolarNodes>,
     //point_set
     point_set_nodes: Metarena<PointSetNodes>,
     //iterative_function
     iterative_function_nodes: Metarena<IterativeFunctionNodes>,
+    escape_time_nodes: Metarena<EscapeTimeNodes>,
     //complex
     sncomplex_nodes: Metarena<SNComplexNodes>,
     //constraint_resolvers
@@ -43,6 +49,9 @@ pub struct NodeSet {
     ufloat_normaliser_nodes: Metarena<UFloatNormaliserNodes>,
     //frame_renderers
     frame_renderer_nodes: Metarena<FrameRendererNodes>,
+    blend_mode_nodes: Metarena<BlendModeNodes>,
+    //sdf
+    sdf_nodes: Metarena<SdfNodes>,
 }
 
 impl NodeSet {
@@ -52,7 +61,8 @@ impl NodeSet {
 
     //ensure this is updated for accurate counts. Don't rely on this for anything
     pub fn count_all(&self) -> usize {
-        self.color_blend_nodes.len()
+        self.binary_automata_nodes.len()
+            + self.color_blend_nodes.len()
             + self.bit_color_nodes.len()
             + self.byte_color_nodes.len()
             + self.float_color_nodes.len()
@@ -70,12 +80,16 @@ impl NodeSet {
             + self.sint_nodes.len()
             + self.snfloat_matrix3_nodes.len()
             + self.snpoint_nodes.len()
+            + self.snpolar_nodes.len()
             + self.point_set_nodes.len()
             + self.iterative_function_nodes.len()
+            + self.escape_time_nodes.len()
             + self.sncomplex_nodes.len()
             + self.sfloat_normaliser_nodes.len()
             + self.ufloat_normaliser_nodes.len()
             + self.frame_renderer_nodes.len()
+            + self.blend_mode_nodes.len()
+            + self.sdf_nodes.len()
     }
 }
 
@@ -85,6 +99,16 @@ impl<'a> Updatable<'a> for NodeSet {
     fn update(&mut self, _arg: Self::UpdateArg) {}
 }
 
+impl Storage<BinaryAutomataNodes> for NodeSet {
+    fn arena(&self) -> &Arena<ArenaSlot<BinaryAutomataNodes>> {
+        &self.binary_automata_nodes.value
+    }
+
+    fn arena_mut(&mut self) -> &mut Arena<ArenaSlot<BinaryAutomataNodes>> {
+        &mut self.binary_automata_nodes.value
+    }
+}
+
 impl Storage<ColorBlendNodes> for NodeSet {
     fn arena(&self) -> &Arena<ArenaSlot<ColorBlendNodes>> {
         &self.color_blend_nodes.value
@@ -255,6 +279,16 @@ impl Storage<SNPointNodes> for NodeSet {
     }
 }
 
+impl Storage<SNPolarNodes> for NodeSet {
+    fn arena(&self) -> &Arena<ArenaSlot<SNPolarNodes>> {
+        &self.snpolar_nodes.value
+    }
+
+    fn arena_mut(&mut self) -> &mut Arena<ArenaSlot<SNPolarNodes>> {
+        &mut self.snpolar_nodes.value
+    }
+}
+
 impl Storage<PointSetNodes> for NodeSet {
     fn arena(&self) -> &Arena<ArenaSlot<PointSetNodes>> {
         &self.point_set_nodes.value
@@ -275,6 +309,16 @@ impl Storage<IterativeFunctionNodes> for NodeSet {
     }
 }
 
+impl Storage<EscapeTimeNodes> for NodeSet {
+    fn arena(&self) -> &Arena<ArenaSlot<EscapeTimeNodes>> {
+        &self.escape_time_nodes.value
+    }
+
+    fn arena_mut(&mut self) -> &mut Arena<ArenaSlot<EscapeTimeNodes>> {
+        &mut self.escape_time_nodes.value
+    }
+}
+
 impl Storage<SNComplexNodes> for NodeSet {
     fn arena(&self) -> &Arena<ArenaSlot<SNComplexNodes>> {
         &self.sncomplex_nodes.value
@@ -325,6 +369,16 @@ impl Storage<SIntNodes> for NodeSet {
     }
 }
 
+impl Storage<BlendModeNodes> for NodeSet {
+    fn arena(&self) -> &Arena<ArenaSlot<BlendModeNodes>> {
+        &self.blend_mode_nodes.value
+    }
+
+    fn arena_mut(&mut self) -> &mut Arena<ArenaSlot<BlendModeNodes>> {
+        &mut self.blend_mode_nodes.value
+    }
+}
+
 impl Storage<FrameRendererNodes> for NodeSet {
     fn arena(&self) -> &Arena<ArenaSlot<FrameRendererNodes>> {
         &self.frame_renderer_nodes.value
@@ -334,3 +388,13 @@ impl Storage<FrameRendererNodes> for NodeSet {
         &mut self.frame_renderer_nodes.value
     }
 }
+
+impl Storage<SdfNodes> for NodeSet {
+    fn arena(&self) -> &Arena<ArenaSlot<SdfNodes>> {
+        &self.sdf_nodes.value
+    }
+
+    fn arena_mut(&mut self) -> &mut Arena<ArenaSlot<SdfNodes>> {
+        &mut self.sdf_nodes.value
+    }
+}
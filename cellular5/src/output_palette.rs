@@ -0,0 +1,278 @@
+use std::{fs, path::Path};
+
+use failure::{bail, format_err, Fallible};
+use ndarray::ArrayViewMut3;
+
+use crate::prelude::*;
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A fixed set of colours that every computed cell is snapped to before it's written
+#[derive(Debug, Clone)]
+pub struct OutputPalette {
+    colors: Vec<[f32; 3]>,
+    dithering: Dithering,
+    //How far ordered dithering can push a colour, based on how far apart the palette colours are
+    ordered_spread: f32,
+}
+
+impl OutputPalette {
+    pub fn new(colors: Vec<[u8; 3]>, dithering: Dithering) -> Fallible<Self> {
+        if colors.is_empty() {
+            bail!("Output palette has no colours");
+        }
+
+        let colors: Vec<[f32; 3]> = colors
+            .into_iter()
+            .map(|[r, g, b]| [f32::from(r), f32::from(g), f32::from(b)])
+            .collect();
+
+        let ordered_spread = if colors.len() > 1 {
+            colors
+                .iter()
+                .enumerate()
+                .map(|(i, a)| {
+                    colors
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| i != *j)
+                        .map(|(_, b)| distance_squared(*a, *b).sqrt())
+                        .fold(f32::MAX, f32::min)
+                })
+                .sum::<f32>()
+                / colors.len() as f32
+        } else {
+            0.0
+        };
+
+        Ok(Self {
+            colors,
+            dithering,
+            ordered_spread,
+        })
+    }
+
+    pub fn load(config: &OutputPaletteConfig) -> Fallible<Self> {
+        let mut colors = config
+            .colors
+            .iter()
+            .map(|color| parse_hex_color(color))
+            .collect::<Fallible<Vec<_>>>()?;
+
+        if let Some(file) = &config.file {
+            colors.extend(load_palette_file(file)?);
+        }
+
+        Self::new(colors, config.dithering)
+    }
+
+    fn nearest(&self, rgb: [f32; 3]) -> [f32; 3] {
+        *self
+            .colors
+            .iter()
+            .min_by(|a, b| {
+                distance_squared(rgb, **a)
+                    .partial_cmp(&distance_squared(rgb, **b))
+                    .unwrap()
+            })
+            .unwrap()
+    }
+
+    /// Snaps a single cell to the palette. With error diffusion the colour is left alone here,
+    /// since diffuse_errors needs the unquantised slice.
+    pub fn quantise(&self, color: ByteColor, x: usize, y: usize) -> ByteColor {
+        let rgb = [
+            f32::from(color.r.into_inner()),
+            f32::from(color.g.into_inner()),
+            f32::from(color.b.into_inner()),
+        ];
+
+        let quantised = match self.dithering {
+            Dithering::None => self.nearest(rgb),
+            Dithering::Ordered => {
+                let threshold = (f32::from(BAYER_4X4[y % 4][x % 4]) + 0.5) / 16.0 - 0.5;
+                let offset = threshold * self.ordered_spread;
+
+                self.nearest([rgb[0] + offset, rgb[1] + offset, rgb[2] + offset])
+            }
+            Dithering::ErrorDiffusion => return color,
+        };
+
+        ByteColor {
+            r: Byte::new(quantised[0] as u8),
+            g: Byte::new(quantised[1] as u8),
+            b: Byte::new(quantised[2] as u8),
+            a: color.a,
+        }
+    }
+
+    /// Floyd-Steinberg over the whole (y, x, rgba) cell array, once every slice has been computed
+    pub fn diffuse_errors(&self, mut cells: ArrayViewMut3<u8>) {
+        if self.dithering != Dithering::ErrorDiffusion {
+            return;
+        }
+
+        let (height, width, _) = cells.dim();
+
+        //Padded by a cell either side so the kernel never has to check bounds
+        let mut current_errors = vec![[0.0_f32; 3]; width + 2];
+        let mut next_errors = vec![[0.0_f32; 3]; width + 2];
+
+        for y in 0..height {
+            for x in 0..width {
+                let channel = |c: usize| f32::from(cells[[y, x, c]]) + current_errors[x + 1][c];
+                let rgb = [channel(0), channel(1), channel(2)];
+
+                let quantised = self.nearest(rgb);
+
+                for (c, &value) in quantised.iter().enumerate() {
+                    cells[[y, x, c]] = value as u8;
+
+                    let error = rgb[c] - value;
+                    current_errors[x + 2][c] += error * 7.0 / 16.0;
+                    next_errors[x][c] += error * 3.0 / 16.0;
+                    next_errors[x + 1][c] += error * 5.0 / 16.0;
+                    next_errors[x + 2][c] += error * 1.0 / 16.0;
+                }
+            }
+
+            std::mem::swap(&mut current_errors, &mut next_errors);
+            next_errors.iter_mut().for_each(|error| *error = [0.0; 3]);
+        }
+    }
+
+    /// A Shadertoy style pass that snaps every pixel of the drawn frame to its nearest palette
+    /// colour. Dithering is left to the cell array, so this only catches colours made by blending.
+    pub fn shadertoy_source(&self) -> String {
+        let colors: Vec<_> = self
+            .colors
+            .iter()
+            .map(|[r, g, b]| {
+                format!("vec3({:.6}, {:.6}, {:.6})", r / 255.0, g / 255.0, b / 255.0)
+            })
+            .collect();
+
+        format!(
+            "const int PALETTE_SIZE = {};
+const vec3 PALETTE[PALETTE_SIZE] = vec3[]({});
+
+void mainImage(out vec4 fragColor, in vec2 fragCoord) {{
+    vec3 color = texture(iTexture, fragCoord / iResolution.xy).rgb;
+    vec3 nearest = PALETTE[0];
+
+    for (int i = 1; i < PALETTE_SIZE; i++) {{
+        vec3 offset = PALETTE[i] - color;
+        vec3 nearest_offset = nearest - color;
+
+        if (dot(offset, offset) < dot(nearest_offset, nearest_offset)) {{
+            nearest = PALETTE[i];
+        }}
+    }}
+
+    fragColor = vec4(nearest, 1.0);
+}}
+",
+            colors.len(),
+            colors.join(", ")
+        )
+    }
+}
+
+fn distance_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+fn parse_hex_color(color: &str) -> Fallible<[u8; 3]> {
+    let hex = color.trim().trim_start_matches('#');
+
+    if hex.len() != 6 || !hex.is_ascii() {
+        bail!("Invalid hex colour: {}", color);
+    }
+
+    let component = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16)
+            .map_err(|e| format_err!("Invalid hex colour {}: {}", color, e))
+    };
+
+    Ok([component(0)?, component(2)?, component(4)?])
+}
+
+/// Reads a GIMP .gpl palette, or a .hex file with one colour per line
+fn load_palette_file<P: AsRef<Path>>(path: P) -> Fallible<Vec<[u8; 3]>> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format_err!("Couldn't read {}: {}", path.to_string_lossy(), e))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gpl") => parse_gpl(&contents),
+        Some("hex") => parse_hex(&contents),
+        _ => bail!("Unknown palette format: {}", path.to_string_lossy()),
+    }
+}
+
+fn parse_gpl(contents: &str) -> Fallible<Vec<[u8; 3]>> {
+    let mut lines = contents.lines();
+
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        bail!("Missing GIMP Palette header");
+    }
+
+    lines
+        .map(str::trim)
+        .filter(|line| {
+            !line.is_empty()
+                && !line.starts_with('#')
+                && !line.starts_with("Name:")
+                && !line.starts_with("Columns:")
+        })
+        .map(|line| {
+            let mut components = line.split_whitespace().map(|component| {
+                component
+                    .parse::<u8>()
+                    .map_err(|e| format_err!("Invalid palette line {:?}: {}", line, e))
+            });
+
+            let mut next = || {
+                components
+                    .next()
+                    .unwrap_or_else(|| Err(format_err!("Invalid palette line {:?}", line)))
+            };
+
+            Ok([next()?, next()?, next()?])
+        })
+        .collect()
+}
+
+fn parse_hex(contents: &str) -> Fallible<Vec<[u8; 3]>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(parse_hex_color)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_palette_files() {
+        let gpl = "GIMP Palette\nName: Brand\nColumns: 2\n#\n  0   0   0\tBlack\n228 0 43 Red\n";
+        let hex = "000000\n#E4002B\n\n";
+
+        assert_eq!(parse_gpl(gpl).unwrap(), vec![[0, 0, 0], [228, 0, 43]]);
+        assert_eq!(parse_hex(hex).unwrap(), vec![[0, 0, 0], [228, 0, 43]]);
+        assert!(parse_hex("12345").is_err());
+    }
+
+    #[test]
+    fn palette_shader_lists_every_colour() {
+        let palette = OutputPalette::new(vec![[0, 0, 0], [255, 51, 0]], Dithering::None).unwrap();
+        let source = palette.shadertoy_source();
+
+        assert!(source.contains("PALETTE_SIZE = 2;"));
+        assert!(source.contains("vec3(0.000000, 0.000000, 0.000000)"));
+        assert!(source.contains("vec3(1.000000, 0.200000, 0.000000)"));
+    }
+}
@@ -0,0 +1,265 @@
+use std::{fs, path::Path, time::Instant};
+
+use failure::{bail, format_err, Fallible};
+use ggez::{
+    graphics::{self, Canvas, Color as GgColor, DrawParam, Rect, Shader},
+    Context, GameResult,
+};
+use log::warn;
+
+use crate::{prelude::*, update_stat::UpdateStat};
+
+gfx_defines! {
+    constant ShadertoyUniforms {
+        resolution: [f32; 3] = "iResolution",
+        time: f32 = "iTime",
+        stats: [f32; 4] = "iStats",
+        mic: [f32; 4] = "iMic",
+    }
+}
+
+pub type ShadertoyShader = Shader<ShadertoyUniforms>;
+
+const SHADERTOY_HEADER: &str = "#version 150 core
+
+uniform sampler2D t_Texture;
+in vec2 v_Uv;
+in vec4 v_Color;
+out vec4 Target0;
+
+layout (std140) uniform Shadertoy {
+    vec3 iResolution;
+    float iTime;
+    vec4 iStats;
+    vec4 iMic;
+};
+
+#define iTexture t_Texture
+#define iChannel0 t_Texture
+
+";
+
+const SHADERTOY_FOOTER: &str = "
+
+void main() {
+    mainImage(Target0, gl_FragCoord.xy);
+}
+";
+
+/// Wraps a shader written against Shadertoy's mainImage entry point into a full fragment shader
+pub fn shadertoy_fragment_source(source: &str) -> String {
+    format!("{}{}{}", SHADERTOY_HEADER, source, SHADERTOY_FOOTER)
+}
+
+/// Builds a ggez shader from a Shadertoy style shader, which only defines mainImage
+pub fn new_shadertoy(ctx: &mut Context, source: &str) -> Fallible<ShadertoyShader> {
+    let vertex_shader =
+        include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders/ggez_vertex.glsl"));
+
+    Shader::from_u8(
+        ctx,
+        vertex_shader,
+        shadertoy_fragment_source(source).as_bytes(),
+        ShadertoyUniforms {
+            resolution: [1.0; 3],
+            time: 0.0,
+            stats: [0.0; 4],
+            mic: [0.0; 4],
+        },
+        "Shadertoy",
+        None,
+    )
+    .map_err(|e| format_err!("Failed to build shader: {}", e))
+}
+
+/// A chain of Shadertoy style fragment shaders run over the frame once the frame renderers are
+/// done. The frame is drawn into a canvas, and each pass draws over a copy of the previous pass's
+/// canvas in the other of two scratch canvases, sampling the previous one through iTexture.
+pub struct PostProcess {
+    passes: Vec<ShadertoyShader>,
+    frame: Canvas,
+    scratch: [Canvas; 2],
+    start_time: Instant,
+}
+
+impl PostProcess {
+    pub fn new(ctx: &mut Context, passes: Vec<ShadertoyShader>) -> GameResult<Self> {
+        Ok(Self {
+            passes,
+            frame: Canvas::with_window_size(ctx)?,
+            scratch: [
+                Canvas::with_window_size(ctx)?,
+                Canvas::with_window_size(ctx)?,
+            ],
+            start_time: Instant::now(),
+        })
+    }
+
+    pub fn load(ctx: &mut Context, config: &PostProcessConfig) -> Fallible<Self> {
+        let mut paths: Vec<_> = fs::read_dir(Path::new(&config.shader_dir))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |ext| ext == "glsl"))
+            .collect();
+        paths.sort();
+
+        //A shader that fails to compile is skipped rather than taking down the whole chain
+        let passes: Vec<_> = paths
+            .iter()
+            .filter_map(|path| {
+                fs::read_to_string(path)
+                    .map_err(failure::Error::from)
+                    .and_then(|source| new_shadertoy(ctx, &source))
+                    .map_err(|e| warn!("Failed to load shader {}: {}", path.display(), e))
+                    .ok()
+            })
+            .collect();
+
+        if passes.is_empty() {
+            bail!("No usable shaders in {}", config.shader_dir);
+        }
+
+        Self::new(ctx, passes).map_err(|e| format_err!("Failed to create canvases: {}", e))
+    }
+
+    /// Adds a pass after the ones already loaded
+    pub fn push_pass(&mut self, pass: ShadertoyShader) {
+        self.passes.push(pass);
+    }
+
+    /// The canvases are made at the window's size, so they have to be remade when it changes
+    pub fn resize(&mut self, ctx: &mut Context) -> GameResult<()> {
+        self.frame = Canvas::with_window_size(ctx)?;
+        self.scratch = [
+            Canvas::with_window_size(ctx)?,
+            Canvas::with_window_size(ctx)?,
+        ];
+
+        Ok(())
+    }
+
+    /// The canvas the frame has to be drawn into before draw is called
+    pub fn frame(&self) -> &Canvas {
+        &self.frame
+    }
+
+    /// Redirects drawing into the frame canvas until draw is called
+    pub fn begin(&self, ctx: &mut Context) {
+        graphics::set_canvas(ctx, Some(&self.frame));
+    }
+
+    /// Runs every pass over the frame canvas and draws the result to the window
+    pub fn draw(
+        &self,
+        ctx: &mut Context,
+        update_stat: &UpdateStat,
+        mic_spectrograms: Option<&FrequencySpectrograms>,
+    ) -> GameResult<()> {
+        let width = f32::from(self.frame.image().width());
+        let height = f32::from(self.frame.image().height());
+
+        let uniforms = ShadertoyUniforms {
+            resolution: [width, height, 1.0],
+            time: self.start_time.elapsed().as_secs_f32(),
+            stats: [
+                update_stat.activity_value as f32,
+                update_stat.alpha_value as f32,
+                update_stat.local_similarity_value as f32,
+                update_stat.global_similarity_value as f32,
+            ],
+            mic: mic_spectrograms
+                .map(|spectrograms| mic_bands(spectrograms.get_spectrogram(true)))
+                .unwrap_or([0.0; 4]),
+        };
+
+        //Canvases are drawn pixel for pixel, whatever coordinates the frame was drawn in
+        let screen_coordinates = graphics::screen_coordinates(ctx);
+        graphics::set_screen_coordinates(ctx, Rect::new(0.0, 0.0, width, height))?;
+
+        let mut source = &self.frame;
+
+        for (pass, target) in self.passes.iter().zip(self.scratch.iter().cycle()) {
+            graphics::set_canvas(ctx, Some(target));
+            graphics::clear(ctx, GgColor::BLACK);
+
+            //Passes are drawn over a copy of their input so they can blend with it, the same as
+            //they would with the frame on screen
+            graphics::draw(ctx, source, DrawParam::new())?;
+
+            {
+                let _lock = graphics::use_shader(ctx, pass);
+                pass.send(ctx, uniforms)?;
+                graphics::draw(ctx, source, DrawParam::new())?;
+            }
+
+            source = target;
+        }
+
+        graphics::set_canvas(ctx, None);
+        graphics::draw(ctx, source, DrawParam::new())?;
+
+        graphics::set_screen_coordinates(ctx, screen_coordinates)
+    }
+}
+
+/// Averages the spectrogram into bass, mid and treble thirds, followed by the loudest bin
+fn mic_bands(spectrogram: &FrequencySpectrogram) -> [f32; 4] {
+    let bins = spectrogram.bins().len();
+
+    if bins == 0 {
+        return [0.0; 4];
+    }
+
+    let band = |from: usize, to: usize| {
+        let to = to.max(from + 1).min(bins);
+
+        (from..to)
+            .map(|i| spectrogram.get_normalised(i).into_inner())
+            .sum::<f32>()
+            / (to - from) as f32
+    };
+
+    let peak = (0..bins)
+        .map(|i| spectrogram.get_normalised(i).into_inner())
+        .fold(0.0, f32::max);
+
+    [
+        band(0, bins / 3),
+        band(bins / 3, bins * 2 / 3),
+        band(bins * 2 / 3, bins),
+        peak,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadertoy_source_is_wrapped() {
+        let source = shadertoy_fragment_source(
+            "void mainImage(out vec4 fragColor, in vec2 fragCoord) { fragColor = vec4(1.0); }",
+        );
+
+        assert!(source.starts_with("#version"));
+        assert!(source.contains("vec4 iStats;"));
+        assert!(source.contains("#define iChannel0"));
+        assert!(source.trim_end().ends_with('}'));
+        assert!(source.find("mainImage(out").unwrap() < source.find("void main()").unwrap());
+    }
+
+    #[test]
+    #[ignore = "needs a display, run with LIBGL_ALWAYS_SOFTWARE=1 xvfb-run and --test-threads=1"]
+    fn basic_shader_compiles() {
+        let (mut ctx, _event_loop) = ggez::ContextBuilder::new("cellular5_shader_test", "cellular5")
+            .build()
+            .unwrap();
+
+        let source = fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/shaders/shadertoy/basic.glsl"
+        ))
+        .unwrap();
+
+        new_shadertoy(&mut ctx, &source).unwrap();
+    }
+}
@@ -6,7 +6,9 @@ pub use crate::{
     coordinate_set::*,
     data_set::*,
     datatype::{
-        frame_renderers::*, image::*,
+        blend::*, escape_time::*, flock::*, frame_renderers::*, gray_scott::*, image::*, kernel::*,
+        lenia::*, lsystem::*, palette::*, physarum::*, polar::*, sdf::*, sequence::*,
+        totalistic::*, turmite::*, worley::*,
     },
     gamepad::*,
     history::*,
@@ -16,7 +18,7 @@ pub use crate::{
         automata_nodes::*, color_blend_nodes::*, color_nodes::*, complex_nodes::*,
         constraint_resolver_nodes::*, continuous_nodes::*, coord_map_nodes::*, discrete_nodes::*,
         frame_renderer_nodes::*, iterative_function_nodes::*, matrix_nodes::*,
-        mutagen_functions::*, point_nodes::*, point_set_nodes::*, Node,
+        mutagen_functions::*, point_nodes::*, point_set_nodes::*, sdf_nodes::*, Node,
     },
     node_set::*,
     preloader::*,
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use lerp::Lerp;
+use log::info;
+
+use crate::{prelude::*, video_wall::VideoWall};
+
+/// Tracks the current cell array resolution and scales it with the time each update takes,
+/// relative to the configured cell array size and tics per update.
+#[derive(Debug)]
+pub struct Resolution {
+    scale: f32,
+    //Video wall tiles that zoom in on the frame need more cells to stay as sharp as the frame
+    wall_scale: f32,
+    updates_since_change: usize,
+    average_load: f64,
+}
+
+impl Resolution {
+    pub fn new() -> Self {
+        Self {
+            scale: 1.0,
+            wall_scale: CONSTS
+                .video_wall
+                .as_ref()
+                .filter(|config| VideoWall::validate(config).is_ok())
+                .map_or(1.0, VideoWall::resolution_scale),
+            updates_since_change: 0,
+            average_load: 0.0,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        ((CONSTS.cell_array_width as f32 * self.scale * self.wall_scale).round() as usize).max(1)
+    }
+
+    pub fn height(&self) -> usize {
+        ((CONSTS.cell_array_height as f32 * self.scale * self.wall_scale).round() as usize).max(1)
+    }
+
+    pub fn tics_per_update(&self) -> usize {
+        ((CONSTS.tics_per_update as f32 * self.scale).round() as usize)
+            .max(1)
+            .min(self.height())
+    }
+
+    /// Records how long the last update took to compute, returning true if the resolution changed
+    pub fn record_update(&mut self, busy_time: Duration) -> bool {
+        let config = if let Some(config) = &CONSTS.adaptive_resolution {
+            config
+        } else {
+            return false;
+        };
+
+        let target_time = 1.0 / CONSTS.target_fps as f64;
+        let load = busy_time.as_secs_f64() / target_time;
+
+        self.average_load = self.average_load.lerp(load, config.load_lerp_factor);
+        self.updates_since_change += 1;
+
+        if self.updates_since_change < config.cooldown_updates {
+            return false;
+        }
+
+        let new_scale = if self.average_load > config.upper_load {
+            (self.scale - config.scale_step).max(config.min_scale)
+        } else if self.average_load < config.lower_load {
+            (self.scale + config.scale_step).min(1.0)
+        } else {
+            self.scale
+        };
+
+        let (old_width, old_height) = (self.width(), self.height());
+        self.scale = new_scale;
+
+        if (old_width, old_height) != (self.width(), self.height()) {
+            info!(
+                "Load {:.2}, resizing cell array from {}x{} to {}x{}",
+                self.average_load,
+                old_width,
+                old_height,
+                self.width(),
+                self.height()
+            );
+
+            self.updates_since_change = 0;
+            self.average_load = (config.lower_load + config.upper_load) * 0.5;
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Default for Resolution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
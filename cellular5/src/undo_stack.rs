@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+
+use failure::Fallible;
+use serde::{de::DeserializeOwned, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndoDirection {
+    Back,
+    Forward,
+}
+
+/// A bounded stack of serialized snapshots that can be stepped back and forth through
+#[derive(Debug)]
+pub struct UndoStack {
+    capacity: usize,
+    undo: VecDeque<Vec<u8>>,
+    redo: Vec<Vec<u8>>,
+}
+
+impl UndoStack {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+        }
+    }
+
+    /// Records the state before a change, discarding anything that could previously be redone
+    pub fn push<S: Serialize>(&mut self, state: &S) -> Fallible<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let snapshot = serde_yaml::to_vec(state)?;
+
+        if self.undo.len() >= self.capacity {
+            self.undo.pop_front();
+        }
+
+        self.undo.push_back(snapshot);
+        self.redo.clear();
+
+        Ok(())
+    }
+
+    /// Steps through the stack, storing the current state on the other side so it can be returned to.
+    /// Returns None if there's nothing to step to.
+    pub fn step<S: Serialize, D: DeserializeOwned>(
+        &mut self,
+        direction: UndoDirection,
+        current: &S,
+    ) -> Fallible<Option<D>> {
+        let snapshot = match direction {
+            UndoDirection::Back => self.undo.pop_back(),
+            UndoDirection::Forward => self.redo.pop(),
+        };
+
+        let snapshot = if let Some(snapshot) = snapshot {
+            snapshot
+        } else {
+            return Ok(None);
+        };
+
+        let restored = serde_yaml::from_slice(&snapshot)?;
+        let current = serde_yaml::to_vec(current)?;
+
+        match direction {
+            UndoDirection::Back => self.redo.push(current),
+            UndoDirection::Forward => self.undo.push_back(current),
+        }
+
+        Ok(Some(restored))
+    }
+
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+
+    pub fn redo_len(&self) -> usize {
+        self.redo.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_back_and_forward() {
+        let mut stack = UndoStack::new(2);
+
+        stack.push(&1).unwrap();
+        stack.push(&2).unwrap();
+        stack.push(&3).unwrap();
+        assert_eq!(stack.undo_len(), 2);
+
+        assert_eq!(stack.step(UndoDirection::Back, &4).unwrap(), Some(3));
+        assert_eq!(stack.step(UndoDirection::Back, &3).unwrap(), Some(2));
+        assert_eq!(stack.step::<_, i32>(UndoDirection::Back, &2).unwrap(), None);
+
+        assert_eq!(stack.step(UndoDirection::Forward, &2).unwrap(), Some(3));
+        assert_eq!(stack.step(UndoDirection::Forward, &3).unwrap(), Some(4));
+        assert_eq!(stack.redo_len(), 0);
+
+        stack.step::<_, i32>(UndoDirection::Back, &4).unwrap();
+        stack.push(&5).unwrap();
+        assert_eq!(stack.redo_len(), 0);
+    }
+}
@@ -4,10 +4,11 @@ use std::{
 };
 
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::constants::*;
 
-#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct UpdateStat {
     //Update stats are used to determine an approximation of the entropy of the current state
     //Update stats contain many values:
@@ -0,0 +1,186 @@
+use failure::{bail, format_err, Fallible};
+use ggez::{
+    graphics::{self, Canvas, Color as GgColor, DrawParam, Rect},
+    winit::dpi::PhysicalPosition,
+    Context, GameResult,
+};
+
+use crate::prelude::*;
+
+/// Splits the rendered frame over several regions of one borderless window, so a single window
+/// spanning every display can drive a video wall. The frame renderers draw into a canvas in the
+/// usual initial_window_width by initial_window_height coordinates, which each tile then shows
+/// a part of.
+pub struct VideoWall {
+    canvas: Canvas,
+    tiles: Vec<VideoWallTileConfig>,
+    width: f32,
+    height: f32,
+}
+
+impl VideoWall {
+    /// The most the cell array is scaled up by to keep zoomed in tiles sharp
+    const MAX_RESOLUTION_SCALE: f32 = 4.0;
+
+    pub fn validate(config: &VideoWallConfig) -> Fallible<()> {
+        if config.tiles.is_empty() {
+            bail!("Video wall has no tiles");
+        }
+
+        for (i, tile) in config.tiles.iter().enumerate() {
+            if !(tile.width > 0.0 && tile.height > 0.0) {
+                bail!("Video wall tile {} has no area", i);
+            }
+
+            if !(tile.scale[0] > 0.0 && tile.scale[1] > 0.0) {
+                bail!("Video wall tile {} has a scale of zero or less", i);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// How much the cell array has to be scaled up by for the most zoomed in tile to show as
+    /// many cells across as the whole frame would
+    pub fn resolution_scale(config: &VideoWallConfig) -> f32 {
+        config
+            .tiles
+            .iter()
+            .map(|tile| 1.0 / tile.scale[0].min(tile.scale[1]))
+            .fold(1.0, f32::max)
+            .min(Self::MAX_RESOLUTION_SCALE)
+    }
+
+    /// The window size needed to fit every tile
+    pub fn window_size(config: &VideoWallConfig) -> (f32, f32) {
+        config.tiles.iter().fold((0.0, 0.0), |(width, height), tile| {
+            (width.max(tile.x + tile.width), height.max(tile.y + tile.height))
+        })
+    }
+
+    pub fn new(ctx: &mut Context, config: &VideoWallConfig) -> Fallible<Self> {
+        Self::validate(config)?;
+
+        let (width, height) = Self::window_size(config);
+
+        //The window has to start at the desktop origin to line up with the displays
+        graphics::set_window_position(ctx, PhysicalPosition::new(0, 0))
+            .map_err(|e| format_err!("Failed to move window: {}", e))?;
+
+        Ok(Self {
+            canvas: Canvas::with_window_size(ctx)
+                .map_err(|e| format_err!("Failed to create canvas: {}", e))?,
+            tiles: config.tiles.clone(),
+            width,
+            height,
+        })
+    }
+
+    /// Redirects drawing into the frame canvas until end is called
+    pub fn begin(&self, ctx: &mut Context) -> GameResult<()> {
+        graphics::set_canvas(ctx, Some(&self.canvas));
+        set_frame_coordinates(ctx)
+    }
+
+    /// Draws each tile's part of the frame canvas to the window, or to target if there is one
+    pub fn end(&self, ctx: &mut Context, target: Option<&Canvas>) -> GameResult<()> {
+        graphics::set_canvas(ctx, target);
+        graphics::set_screen_coordinates(ctx, Rect::new(0.0, 0.0, self.width, self.height))?;
+        graphics::clear(ctx, GgColor::BLACK);
+
+        let canvas_width = f32::from(self.canvas.image().width());
+        let canvas_height = f32::from(self.canvas.image().height());
+
+        for tile in &self.tiles {
+            let src = src_rect(tile);
+
+            graphics::draw(
+                ctx,
+                &self.canvas,
+                DrawParam::new().src(src).dest([tile.x, tile.y]).scale([
+                    tile.width / (src.w * canvas_width),
+                    tile.height / (src.h * canvas_height),
+                ]),
+            )?;
+        }
+
+        //Anything drawn afterwards, such as the library browser, goes over the whole wall
+        set_frame_coordinates(ctx)
+    }
+}
+
+/// The part of the frame canvas a tile shows, in texture coordinates
+fn src_rect(tile: &VideoWallTileConfig) -> Rect {
+    let [offset_x, offset_y] = tile.offset;
+    let [scale_x, scale_y] = tile.scale;
+
+    Rect::new(
+        (offset_x + 1.0) * 0.5 - scale_x * 0.5,
+        (offset_y + 1.0) * 0.5 - scale_y * 0.5,
+        scale_x,
+        scale_y,
+    )
+}
+
+fn set_frame_coordinates(ctx: &mut Context) -> GameResult<()> {
+    graphics::set_screen_coordinates(
+        ctx,
+        Rect::new(
+            0.0,
+            0.0,
+            CONSTS.initial_window_width,
+            CONSTS.initial_window_height,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(x: f32, offset: [f32; 2], scale: [f32; 2]) -> VideoWallTileConfig {
+        VideoWallTileConfig {
+            x,
+            y: 0.0,
+            width: 1920.0,
+            height: 1080.0,
+            offset,
+            scale,
+        }
+    }
+
+    #[test]
+    fn window_fits_every_tile() {
+        let config = VideoWallConfig {
+            tiles: vec![
+                tile(0.0, [-0.5, 0.0], [0.5, 1.0]),
+                tile(1920.0, [0.5, 0.0], [0.5, 1.0]),
+            ],
+        };
+
+        assert_eq!(VideoWall::window_size(&config), (3840.0, 1080.0));
+        assert_eq!(VideoWall::resolution_scale(&config), 2.0);
+        assert!(VideoWall::validate(&config).is_ok());
+    }
+
+    #[test]
+    fn src_rect_covers_tile_region() {
+        assert_eq!(
+            src_rect(&tile(0.0, [0.0, 0.0], [1.0, 1.0])),
+            Rect::new(0.0, 0.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            src_rect(&tile(0.0, [-0.5, 0.5], [0.5, 0.5])),
+            Rect::new(0.0, 0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn invalid_walls_are_rejected() {
+        assert!(VideoWall::validate(&VideoWallConfig { tiles: Vec::new() }).is_err());
+        assert!(VideoWall::validate(&VideoWallConfig {
+            tiles: vec![tile(0.0, [0.0, 0.0], [0.0, 1.0])],
+        })
+        .is_err());
+    }
+}